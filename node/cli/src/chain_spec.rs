@@ -26,7 +26,8 @@ use node_runtime::{
 	wasm_binary_unwrap, AuthorityDiscoveryConfig, BabeConfig, BalancesConfig, CouncilConfig,
 	DemocracyConfig, EVMConfig, ElectionsConfig, GovConfig, GrandpaConfig, ImOnlineConfig,
 	IndicesConfig, IssueConfig, SessionConfig, SessionKeys, StakerStatus, StakingConfig,
-	SudoConfig, SystemConfig, TechnicalCommitteeConfig,
+	SudoConfig, SystemConfig, TechnicalCommitteeConfig, BridgeCommonConfig, RBalancesConfig,
+	NativeTokenId, AddressFormat, FeeSchedule,
 };
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sc_chain_spec::ChainSpecExtension;
@@ -38,10 +39,11 @@ use sp_consensus_babe::AuthorityId as BabeId;
 use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public};
 use sp_runtime::{
 	traits::{IdentifyAccount, Verify},
-	Perbill,
+	Perbill, Permill,
 };
 
 pub use node_primitives::{AccountId, Balance, Signature};
+use node_primitives::ETH_CHAIN_ID;
 pub use node_runtime::GenesisConfig;
 
 type AccountPublic = <Signature as Verify>::Signer;
@@ -332,7 +334,7 @@ pub fn testnet_genesis(
 				.collect(),
 			phantom: Default::default(),
 		}),
-		pallet_sudo: Some(SudoConfig { key: root_key }),
+		pallet_sudo: Some(SudoConfig { key: root_key.clone() }),
 		pallet_babe: Some(BabeConfig {
 			authorities: vec![],
 		}),
@@ -365,6 +367,26 @@ pub fn testnet_genesis(
 		pallet_gov: Some(GovConfig {
 			vote_period: 604_800_000u64.into(),
 		}),
+		bridge_common: Some(BridgeCommonConfig {
+			relayers: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+			relayer_threshold: 1,
+			chains: vec![ETH_CHAIN_ID],
+			address_formats: vec![(ETH_CHAIN_ID, AddressFormat::Evm)],
+			resources: vec![(NativeTokenId::get(), b"BridgeSwap.transfer_native_back".to_vec())],
+			// AOW has 15 decimals, its ERC20 counterpart 18
+			resource_decimals: vec![(NativeTokenId::get(), (15, 18))],
+			fee_schedules: vec![(ETH_CHAIN_ID, NativeTokenId::get(), FeeSchedule {
+				flat: 10 * DOLLARS,
+				rate: Permill::from_percent(0),
+				min: 10 * DOLLARS,
+				max: 10 * DOLLARS,
+			})],
+			fees_recipient: Some(root_key),
+		}),
+		rtoken_balances: Some(RBalancesConfig {
+			existential_deposit: vec![],
+			balances: vec![],
+		}),
 	}
 }
 
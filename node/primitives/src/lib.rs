@@ -21,9 +21,12 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Encode, Decode};
 use sp_runtime::{
-	generic, traits::{Verify, BlakeTwo256, IdentifyAccount}, OpaqueExtrinsic, MultiSignature
+	generic, traits::{Verify, BlakeTwo256, IdentifyAccount}, OpaqueExtrinsic, MultiSignature, RuntimeDebug,
 };
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -55,6 +58,26 @@ pub type Hash = sp_core::H256;
 /// time scale is milliseconds.
 pub type Timestamp = u64;
 
+/// Identifier of a chain connected through the bridge.
+pub type ChainId = u8;
+
+/// Bridge identifier of Ethereum.
+pub const ETH_CHAIN_ID: ChainId = 2;
+
+/// Symbol of a reward token.
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RSymbol {
+	/// rFIS
+	RFIS,
+	/// rDOT
+	RDOT,
+	/// rKSM
+	RKSM,
+	/// rATOM
+	RATOM,
+}
+
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 /// Header type.
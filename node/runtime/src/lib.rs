@@ -30,7 +30,7 @@ use frame_support::{
 		Weight, IdentityFee,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
-	traits::{Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness, LockIdentifier},
+	traits::{Currency, Filter, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness, LockIdentifier},
 };
 use frame_system::{EnsureRoot, EnsureOneOf};
 // use frame_support::traits::InstanceFilter;
@@ -41,7 +41,7 @@ use sp_core::{
 	OpaqueMetadata,
 };
 pub use node_primitives::{AccountId, Signature};
-use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
+use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment, ChainId};
 use sp_api::impl_runtime_apis;
 use sp_runtime::{
 	Permill, Perbill, Perquintill, Percent, ApplyExtrinsicResult,
@@ -77,6 +77,8 @@ pub use pallet_balances::Call as BalancesCall;
 pub use frame_system::Call as SystemCall;
 #[cfg(any(feature = "std", test))]
 pub use pallet_staking::StakerStatus;
+#[cfg(any(feature = "std", test))]
+pub use bridge_common::{AddressFormat, FeeSchedule};
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
@@ -111,10 +113,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 2,
+	spec_version: 3,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// Native version.
@@ -694,15 +696,131 @@ impl pallet_issue::Trait for Runtime {
 parameter_types! {
 	// 7 days 7 * 24 * 60 * 60 * 1000
 	pub const VoteBlockTime: u64 = 604_800_000;
+	/// Releasing an issue stage needs a third of the issuance to vote, two thirds of it aye
+	pub IssueTallyRule: pallet_gov::TallyRule = pallet_gov::TallyRule {
+		turnout: Perbill::from_percent(33),
+		threshold: pallet_gov::VoteThreshold::SuperMajority(Perbill::from_percent(67)),
+	};
+	pub ProposalTallyRule: pallet_gov::TallyRule = pallet_gov::TallyRule {
+		turnout: Perbill::from_percent(10),
+		threshold: pallet_gov::VoteThreshold::SuperMajorityApprove,
+	};
+}
+
+parameter_types! {
+	pub const GovProposalDeposit: Balance = 100 * DOLLARS;
+	pub const GovMaxClosesPerBlock: u32 = 10;
 }
 
+/// Root, half of the council or a passing general proposal
+type EnsureRootOrHalfCouncilOrGov = EnsureOneOf<
+	AccountId,
+	EnsureRootOrHalfCouncil,
+	pallet_gov::EnsureGov
+>;
+
 impl pallet_gov::Trait for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type Issue = Issue;
 	type VoteBlockTime = VoteBlockTime;
+	type IssueTallyRule = IssueTallyRule;
+	type ProposalTallyRule = ProposalTallyRule;
+	type ProposalDeposit = GovProposalDeposit;
+	type MaxClosesPerBlock = GovMaxClosesPerBlock;
+	type ConvictionLockPeriod = VoteBlockTime;
+	type OpenOrigin = EnsureRootOrHalfCouncilOrGov;
+	type RecoveryOrigin = EnsureRootOrHalfCouncilOrGov;
+	type Origin = Origin;
+	type Proposal = Call;
+	type WeightInfo = weights::pallet_gov::WeightInfo;
+}
+
+parameter_types! {
+	pub const BridgeChainIdentity: ChainId = 1;
+	pub const BridgeProposalLifetime: BlockNumber = 50;
+	pub const BridgeVotesRetention: BlockNumber = 1 * DAYS;
+	pub const BridgeMaxSweepPerBlock: u32 = 50;
+	pub const BridgeDepositRecordsRetention: bridge_common::DepositNonce = 10_000;
+	pub const BridgeExecutedNoncesWindow: bridge_common::DepositNonce = 10_000;
+	pub const BridgeMinFeeSubmissions: u32 = 3;
+	pub const BridgeMaxProxyAccounts: u32 = 16;
+	pub const BridgeMaxFeeSchedules: u32 = 64;
+	pub const BridgeMaxRelayers: u32 = 32;
+	pub const BridgeAdminDelay: BlockNumber = 2 * DAYS;
+	pub const RelayerBond: Balance = 1_000 * DOLLARS;
+	pub const RelayerReward: Balance = 1 * DOLLARS;
+	pub const RelayerSlash: Balance = 100 * DOLLARS;
+}
+
+/// Root, half of the council, a passing general proposal or two thirds of the technical committee
+type EnsureBridgeAdmin = EnsureOneOf<
+	AccountId,
+	EnsureRootOrHalfCouncilOrGov,
+	pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>
+>;
+
+/// Relayer proposals make the inbound calls of the swap pallet, and the remarks of the benchmarks
+pub struct BridgeProposalFilter;
+impl Filter<Call> for BridgeProposalFilter {
+	fn filter(call: &Call) -> bool {
+		#[cfg(feature = "runtime-benchmarks")]
+		{
+			if let Call::System(frame_system::Call::remark(..)) = call {
+				return true;
+			}
+		}
+		bridge_swap::ProposalFilter::<Runtime>::filter(call)
+	}
+}
+
+impl bridge_common::Trait for Runtime {
+	type Event = Event;
+	type AdminOrigin = EnsureBridgeAdmin;
+	type GuardianOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type Proposal = Call;
+	type ProposalFilter = BridgeProposalFilter;
+	type ChainIdentity = BridgeChainIdentity;
+	type ProposalLifetime = BridgeProposalLifetime;
+	type VotesRetention = BridgeVotesRetention;
+	type MaxSweepPerBlock = BridgeMaxSweepPerBlock;
+	type DepositRecordsRetention = BridgeDepositRecordsRetention;
+	type ExecutedNoncesWindow = BridgeExecutedNoncesWindow;
+	type MinFeeSubmissions = BridgeMinFeeSubmissions;
+	type MaxProxyAccounts = BridgeMaxProxyAccounts;
+	type MaxFeeSchedules = BridgeMaxFeeSchedules;
+	type MaxRelayers = BridgeMaxRelayers;
+	type AdminDelay = BridgeAdminDelay;
+	type Currency = Balances;
+	type RelayerBond = RelayerBond;
+	type RelayerReward = RelayerReward;
+	type RelayerSlash = RelayerSlash;
+	type Slash = ();
+	type WeightInfo = weights::bridge_common::WeightInfo;
+}
+
+impl rtoken_balances::Trait for Runtime {
+	type Event = Event;
+	type MintOrigin = EnsureRoot<AccountId>;
+	type BurnOrigin = EnsureRoot<AccountId>;
 }
 
+parameter_types! {
+	pub NativeTokenId: bridge_common::ResourceId = bridge_common::derive_resource_id(BridgeChainIdentity::get(), b"AOW");
+	pub const BridgeLimitPeriod: BlockNumber = 1 * DAYS;
+}
+
+impl bridge_swap::Trait for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type RCurrency = RBalances;
+	type BridgeOrigin = bridge_common::EnsureBridge<Runtime>;
+	type NativeTokenId = NativeTokenId;
+	type NonFungibleHandler = ();
+	type GenericHandler = ();
+	type LimitPeriod = BridgeLimitPeriod;
+	type WeightInfo = weights::bridge_swap::WeightInfo;
+}
 
 construct_runtime!(
 	pub enum Runtime where
@@ -737,7 +855,10 @@ construct_runtime!(
 		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
 		EVM: pallet_evm::{Module, Call, Config, Storage, Event<T>},
 		Issue: pallet_issue::{Module, Call, Config<T>, Storage, Event<T>},
-		Gov: pallet_gov::{Module, Call, Config<T>, Storage, Event<T>},
+		Gov: pallet_gov::{Module, Call, Config<T>, Storage, Event<T>, Origin},
+		BridgeCommon: bridge_common::{Module, Call, Config<T>, Storage, Event<T>},
+		RBalances: rtoken_balances::{Module, Call, Config<T>, Storage, Event<T>},
+		BridgeSwap: bridge_swap::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -930,6 +1051,57 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl bridge_common_rpc_runtime_api::BridgeApi<
+		Block,
+		AccountId,
+		BlockNumber,
+		Balance,
+	> for Runtime {
+		fn pending_proposals(chain_id: ChainId) -> Vec<bridge_common_rpc_runtime_api::ProposalInfo<AccountId, BlockNumber>> {
+			BridgeCommon::pending_proposals(chain_id)
+				.into_iter()
+				.map(|(nonce, call, votes)| bridge_common_rpc_runtime_api::ProposalInfo { nonce, call: call.encode(), votes })
+				.collect()
+		}
+
+		fn vote_status(
+			chain_id: ChainId,
+			nonce: bridge_common::DepositNonce,
+		) -> bridge_common_rpc_runtime_api::NonceStatus<AccountId, BlockNumber> {
+			bridge_common_rpc_runtime_api::NonceStatus {
+				executed: BridgeCommon::nonce_executed(chain_id, nonce),
+				frozen: BridgeCommon::frozen_nonces(chain_id, nonce),
+				proposals: BridgeCommon::nonce_proposals(chain_id, nonce)
+					.into_iter()
+					.map(|(call, votes)| bridge_common_rpc_runtime_api::ProposalInfo { nonce, call: call.encode(), votes })
+					.collect(),
+			}
+		}
+
+		fn quote_fees(
+			dest_id: ChainId,
+			resource_id: bridge_common::ResourceId,
+			amount: Balance,
+		) -> Option<(Balance, bridge_common::FeeCurrency)> {
+			if !BridgeCommon::chain_whitelisted(dest_id) || BridgeCommon::paused_chains(dest_id) {
+				return None;
+			}
+			BridgeCommon::quote_fee(dest_id, resource_id, amount)
+		}
+
+		fn whitelisted_chains() -> Vec<(ChainId, bridge_common::DepositNonce)> {
+			BridgeCommon::whitelisted_chains()
+		}
+
+		fn deposit_records(
+			dest_id: ChainId,
+			from: bridge_common::DepositNonce,
+			limit: u32,
+		) -> Vec<(bridge_common::DepositNonce, bridge_common::DepositRecord<AccountId, BlockNumber>)> {
+			BridgeCommon::deposit_records_from(dest_id, from, limit)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -941,6 +1113,25 @@ impl_runtime_apis! {
 			SessionKeys::decode_into_raw_public_keys(&encoded)
 		}
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn dispatch_benchmark(
+			config: frame_benchmarking::BenchmarkConfig
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{Benchmarking, BenchmarkBatch, add_benchmark};
+
+			let whitelist: Vec<Vec<u8>> = vec![];
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&config, &whitelist);
+
+			add_benchmark!(params, batches, bridge_common, BridgeCommon);
+			add_benchmark!(params, batches, bridge_swap, BridgeSwap);
+
+			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+			Ok(batches)
+		}
+	}
 }
 
 #[cfg(test)]
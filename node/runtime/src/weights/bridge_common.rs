@@ -0,0 +1,166 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights of bridge-common, the pallet defaults until it is benchmarked on the reference hardware.
+//! They are rounded up estimates, not measurements.
+
+use frame_support::weights::{Weight, constants::RocksDbWeight as DbWeight};
+
+pub struct WeightInfo;
+impl bridge_common::WeightInfo for WeightInfo {
+	fn set_threshold() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(2))
+	}
+	fn set_threshold_ratio() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(3, 1))
+	}
+	fn add_resource() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn remove_resource() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn add_relayer() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(5, 4))
+	}
+	fn remove_relayer() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(5, 4))
+	}
+	fn map_resource_and_rsymbol() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(2))
+	}
+	fn unmap_resource_and_rsymbol() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(2))
+	}
+	fn set_resource_decimals() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 3))
+	}
+	fn acknowledge_proposal(r: u32) -> Weight {
+		(250_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads_writes(13, 10))
+			.saturating_add(DbWeight::get().reads_writes(1, 1).saturating_mul(r as Weight))
+	}
+	fn set_relayer_key() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 1))
+	}
+	fn submit_signed_proposal(s: u32) -> Weight {
+		(250_000_000 as Weight)
+			.saturating_add((100_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads_writes(13, 10))
+			.saturating_add(DbWeight::get().reads_writes(3, 1).saturating_mul(s as Weight))
+	}
+	fn reject_proposal(r: u32) -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add((50_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads_writes(10, 5))
+			.saturating_add(DbWeight::get().reads_writes(1, 1).saturating_mul(r as Weight))
+	}
+	fn whitelist_chain() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 1))
+	}
+	fn remove_whitelist_chain() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 1))
+	}
+	fn set_address_format() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn remove_address_format() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 1))
+	}
+	fn set_proxy_accounts() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn remove_proxy_accounts(s: u32) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((150_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads_writes(2, 2))
+			.saturating_add(DbWeight::get().reads_writes(18, 3).saturating_mul(s as Weight))
+	}
+	fn submit_fee_schedule(p: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads_writes(5, 3))
+			.saturating_add(DbWeight::get().reads(1).saturating_mul(p as Weight))
+	}
+	fn set_fee_currency() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn set_fees_recipient_account() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn set_is_pasued() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn pause_chain() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn unpause_chain() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn pause_resource() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn unpause_resource() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1))
+	}
+	fn claim_rewards() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(4, 3))
+	}
+	fn unfreeze_proposal() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(2, 3))
+	}
+	fn retry_proposal(r: u32) -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads_writes(6, 8))
+			.saturating_add(DbWeight::get().reads_writes(1, 1).saturating_mul(r as Weight))
+	}
+	fn attest_source_deposit(r: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((500_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads_writes(9, 2))
+	}
+	fn refund_proposal(r: u32) -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads_writes(8, 10))
+			.saturating_add(DbWeight::get().reads_writes(1, 1).saturating_mul(r as Weight))
+	}
+	fn cancel_admin_action() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(2, 2))
+	}
+}
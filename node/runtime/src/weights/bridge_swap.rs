@@ -0,0 +1,73 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights of bridge-swap, the pallet defaults until it is benchmarked on the reference hardware.
+//! They are rounded up estimates, not measurements.
+
+use frame_support::weights::{Weight, constants::RocksDbWeight as DbWeight};
+
+pub struct WeightInfo;
+impl bridge_swap::WeightInfo for WeightInfo {
+	fn transfer_native() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(14, 8))
+	}
+	fn transfer_native_back() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(7, 4))
+	}
+	fn transfer_rtoken() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(17, 10))
+	}
+	fn transfer_rtoken_back() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(9, 5))
+	}
+	fn set_asset_mode() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(2, 1))
+	}
+	fn set_transfer_limit() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(2))
+	}
+	fn remove_transfer_limit() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 2))
+	}
+	fn release_queued_transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(8, 5))
+	}
+	fn force_release_queued_transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(7, 5))
+	}
+	fn cancel_queued_transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(9, 4))
+	}
+	fn transfer_nonfungible() -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(10, 4))
+	}
+	fn transfer_nonfungible_back() -> Weight {
+		(20_000_000 as Weight)
+	}
+	fn transfer_generic() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(10, 4))
+	}
+	fn execute_generic() -> Weight {
+		(20_000_000 as Weight)
+	}
+}
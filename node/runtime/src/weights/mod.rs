@@ -15,10 +15,13 @@
 
 //! A list of the different weight modules for our runtime.
 
+pub mod bridge_common;
+pub mod bridge_swap;
 pub mod frame_system;
 pub mod pallet_balances;
 pub mod pallet_collective;
 pub mod pallet_democracy;
+pub mod pallet_gov;
 pub mod pallet_identity;
 pub mod pallet_indices;
 pub mod pallet_im_online;
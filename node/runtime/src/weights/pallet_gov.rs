@@ -0,0 +1,60 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights of pallet-gov, conservative estimates until the pallet is benchmarked.
+//! The base weights are round figures above the cost of the computation, the storage accesses
+//! are counted from the code.
+
+use frame_support::weights::{Weight, constants::RocksDbWeight as DbWeight};
+
+pub struct WeightInfo;
+impl pallet_gov::WeightInfo for WeightInfo {
+	fn reset_vote_period() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 1))
+	}
+	fn open_next() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(6, 1))
+	}
+	fn vote() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(9, 8))
+	}
+	fn over() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(8, 6))
+	}
+	fn propose() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(5, 4))
+	}
+	fn vote_proposal() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(9, 7))
+	}
+	fn close_proposal() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(5, 5))
+	}
+	fn unlock() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(10, 8))
+	}
+	fn recover() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(5, 3))
+	}
+	fn schedule_open() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 1))
+	}
+}
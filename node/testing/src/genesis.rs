@@ -112,5 +112,7 @@ pub fn config_endowed(
 		pallet_evm: Some(Default::default()),
 		pallet_issue: Some(Default::default()),
 		pallet_gov: Some(Default::default()),
+		bridge_common: Some(Default::default()),
+		rtoken_balances: Some(Default::default()),
 	}
 }
@@ -0,0 +1,67 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the bridge pallets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Codec, Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use node_primitives::ChainId;
+pub use bridge_common::{DepositNonce, DepositRecord, DepositData, FeeCurrency, ProposalVotes, ResourceId};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// An inbound proposal along with its votes.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ProposalInfo<AccountId, BlockNumber> {
+    /// Deposit nonce on the source chain
+    pub nonce: DepositNonce,
+    /// SCALE encoded call to be dispatched
+    pub call: Vec<u8>,
+    pub votes: ProposalVotes<AccountId, BlockNumber>,
+}
+
+/// Voting state of an inbound (chain, nonce).
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NonceStatus<AccountId, BlockNumber> {
+    /// A proposal for this nonce has been executed
+    pub executed: bool,
+    /// Conflicting proposals were submitted and voting is frozen
+    pub frozen: bool,
+    pub proposals: Vec<ProposalInfo<AccountId, BlockNumber>>,
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait BridgeApi<AccountId, BlockNumber, Balance> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Balance: Codec,
+    {
+        /// Proposals from `chain_id` which are still open for voting.
+        fn pending_proposals(chain_id: ChainId) -> Vec<ProposalInfo<AccountId, BlockNumber>>;
+        /// Voting state of the proposals for an inbound nonce.
+        fn vote_status(chain_id: ChainId, nonce: DepositNonce) -> NonceStatus<AccountId, BlockNumber>;
+        /// Fee charged for a transfer of `amount` of `resource_id` towards `dest_id` and the currency
+        /// it's paid in, `None` if transfers of the resource to that chain are not possible.
+        fn quote_fees(dest_id: ChainId, resource_id: ResourceId, amount: Balance) -> Option<(Balance, FeeCurrency)>;
+        /// Whitelisted chains and their current outbound deposit nonce.
+        fn whitelisted_chains() -> Vec<(ChainId, DepositNonce)>;
+        /// Outbound deposits towards `dest_id` from nonce `from` on, at most `limit` of them.
+        /// Deposits older than the retention window are no longer available.
+        fn deposit_records(dest_id: ChainId, from: DepositNonce, limit: u32) -> Vec<(DepositNonce, DepositRecord<AccountId, BlockNumber>)>;
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the bridge pallets.
+
+use std::sync::Arc;
+use std::convert::{TryFrom, TryInto};
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay}};
+use node_primitives::ChainId;
+pub use bridge_common_rpc_runtime_api::{
+    BridgeApi as BridgeRuntimeApi, DepositNonce, DepositRecord, FeeCurrency, NonceStatus, ProposalInfo, ResourceId,
+};
+
+#[rpc]
+pub trait BridgeApi<BlockHash, AccountId, BlockNumber, Balance> {
+    /// Proposals from `chain_id` which are still open for voting.
+    #[rpc(name = "bridge_pendingProposals")]
+    fn pending_proposals(
+        &self,
+        chain_id: ChainId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<ProposalInfo<AccountId, BlockNumber>>>;
+
+    /// Voting state of the proposals for an inbound nonce.
+    #[rpc(name = "bridge_voteStatus")]
+    fn vote_status(
+        &self,
+        chain_id: ChainId,
+        nonce: DepositNonce,
+        at: Option<BlockHash>,
+    ) -> Result<NonceStatus<AccountId, BlockNumber>>;
+
+    /// Fee charged for a transfer of `amount` of `resource_id` towards `dest_id`,
+    /// along with the currency it's paid in.
+    #[rpc(name = "bridge_quoteFees")]
+    fn quote_fees(
+        &self,
+        dest_id: ChainId,
+        resource_id: ResourceId,
+        amount: NumberOrHex,
+        at: Option<BlockHash>,
+    ) -> Result<Option<(NumberOrHex, FeeCurrency)>>;
+
+    /// Whitelisted chains and their current outbound deposit nonce.
+    #[rpc(name = "bridge_whitelistedChains")]
+    fn whitelisted_chains(&self, at: Option<BlockHash>) -> Result<Vec<(ChainId, DepositNonce)>>;
+
+    /// Outbound deposits towards `dest_id` from nonce `from` on, at most `limit` of them.
+    #[rpc(name = "bridge_depositRecords")]
+    fn deposit_records(
+        &self,
+        dest_id: ChainId,
+        from: DepositNonce,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(DepositNonce, DepositRecord<AccountId, BlockNumber>)>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i64 {
+    fn from(e: Error) -> i64 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+/// Implements the bridge RPC api on top of the `BridgeApi` runtime api.
+pub struct Bridge<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Bridge<C, B> {
+    /// Create new `Bridge` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Bridge { client, _marker: Default::default() }
+    }
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(Error::RuntimeError.into()),
+        message: "Unable to query the bridge state.".into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance> BridgeApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Balance>
+    for Bridge<C, Block>
+where
+    Block: BlockT,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: BridgeRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+    AccountId: Codec,
+    BlockNumber: Codec,
+    Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + TryFrom<NumberOrHex>,
+{
+    fn pending_proposals(
+        &self,
+        chain_id: ChainId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<ProposalInfo<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.pending_proposals(&at, chain_id).map_err(runtime_error)
+    }
+
+    fn vote_status(
+        &self,
+        chain_id: ChainId,
+        nonce: DepositNonce,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<NonceStatus<AccountId, BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.vote_status(&at, chain_id, nonce).map_err(runtime_error)
+    }
+
+    fn quote_fees(
+        &self,
+        dest_id: ChainId,
+        resource_id: ResourceId,
+        amount: NumberOrHex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<(NumberOrHex, FeeCurrency)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let amount = Balance::try_from(amount).map_err(|_| RpcError {
+            code: ErrorCode::InvalidParams,
+            message: "amount doesn't fit in the balance type".into(),
+            data: None,
+        })?;
+        let fees = api.quote_fees(&at, dest_id, resource_id, amount).map_err(runtime_error)?;
+        fees.map(|(f, currency)| f.try_into().map(|f| (f, currency)).map_err(|_| RpcError {
+            code: ErrorCode::InvalidParams,
+            message: format!("{} doesn't fit in NumberOrHex representation", f),
+            data: None,
+        })).transpose()
+    }
+
+    fn whitelisted_chains(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(ChainId, DepositNonce)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.whitelisted_chains(&at).map_err(runtime_error)
+    }
+
+    fn deposit_records(
+        &self,
+        dest_id: ChainId,
+        from: DepositNonce,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(DepositNonce, DepositRecord<AccountId, BlockNumber>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.deposit_records(&at, dest_id, from, limit).map_err(runtime_error)
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recipient address formats of the destination chains.
+
+use sp_std::prelude::*;
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// Longest textual address we try to decode
+const MAX_ADDRESS_LEN: usize = 90;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+/// How recipients of a destination chain are written
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum AddressFormat {
+    /// Raw 20 bytes EVM address
+    Evm,
+    /// SS58 encoded 32 bytes account, with a one byte network prefix
+    Ss58(u8),
+    /// Lowercase bech32 string with the given human readable part
+    Bech32(Vec<u8>),
+}
+
+impl AddressFormat {
+    /// Whether `recipient` is a well formed address of this format
+    pub fn is_valid(&self, recipient: &[u8]) -> bool {
+        match self {
+            AddressFormat::Evm => recipient.len() == 20,
+            AddressFormat::Ss58(prefix) => is_valid_ss58(recipient, *prefix),
+            AddressFormat::Bech32(hrp) => is_valid_bech32(recipient, hrp),
+        }
+    }
+}
+
+fn base58_decode(input: &[u8]) -> Option<Vec<u8>> {
+    // little endian while decoding
+    let mut out: Vec<u8> = Vec::with_capacity(input.len());
+    for c in input {
+        let mut carry = BASE58_ALPHABET.iter().position(|a| a == c)? as u32;
+        for b in out.iter_mut() {
+            carry += (*b as u32) * 58;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            out.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // each leading '1' stands for a zero byte
+    out.extend(input.iter().take_while(|c| **c == b'1').map(|_| 0u8));
+    out.reverse();
+    Some(out)
+}
+
+fn is_valid_ss58(recipient: &[u8], prefix: u8) -> bool {
+    if recipient.len() > MAX_ADDRESS_LEN {
+        return false;
+    }
+    let data = match base58_decode(recipient) {
+        Some(data) => data,
+        None => return false,
+    };
+    // prefix + account + checksum
+    if data.len() != 35 || data[0] != prefix {
+        return false;
+    }
+    let hash = sp_io::hashing::blake2_512(&[SS58_CHECKSUM_PREFIX, &data[..33]].concat());
+    data[33..] == hash[..2]
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    let mut chk: u32 = 1;
+    for v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ (*v as u32);
+        for (i, g) in BECH32_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn is_valid_bech32(recipient: &[u8], hrp: &[u8]) -> bool {
+    if recipient.len() > MAX_ADDRESS_LEN {
+        return false;
+    }
+    let sep = match recipient.iter().rposition(|c| *c == b'1') {
+        Some(sep) => sep,
+        None => return false,
+    };
+    let (prefix, data) = (&recipient[..sep], &recipient[sep + 1..]);
+    // the checksum alone takes 6 characters
+    if prefix != hrp || data.len() < 6 {
+        return false;
+    }
+
+    let mut values: Vec<u8> = prefix.iter().map(|c| c >> 5).collect();
+    values.push(0);
+    values.extend(prefix.iter().map(|c| c & 31));
+    for c in data {
+        match BECH32_CHARSET.iter().position(|a| a == c) {
+            Some(v) => values.push(v as u8),
+            None => return false,
+        }
+    }
+    bech32_polymod(&values) == 1
+}
@@ -0,0 +1,453 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bridge common pallet benchmarking.
+
+use super::*;
+use crate::Module as BridgeCommon;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use sp_core::sr25519;
+use sp_runtime::KeyTypeId;
+
+const SEED: u32 = 0;
+const SRC_ID: ChainId = 1;
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"brdg");
+
+fn resource() -> ResourceId {
+    derive_resource_id(SRC_ID, b"remark")
+}
+
+fn remark<T: Trait>() -> T::Proposal where T::Proposal: From<frame_system::Call<T>> {
+    frame_system::Call::<T>::remark(vec![0; 32]).into()
+}
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+    let who: T::AccountId = account(name, index, SEED);
+    let amount = T::Currency::minimum_balance().saturating_add(T::RelayerBond::get()).saturating_mul(10u32.into());
+    T::Currency::make_free_balance_be(&who, amount);
+    who
+}
+
+/// Whitelists `SRC_ID`, registers its remark resource and adds `n` relayers, all of them needed to pass a proposal.
+fn setup_relayers<T: Trait>(n: u32) -> Result<Vec<T::AccountId>, &'static str> {
+    BridgeCommon::<T>::whitelist(SRC_ID)?;
+    <Resources>::insert(resource(), b"System.remark".to_vec());
+    let mut relayers = Vec::with_capacity(n as usize);
+    for i in 0..n {
+        let relayer = funded_account::<T>("relayer", i);
+        BridgeCommon::<T>::apply_admin_action(AdminAction::AddRelayer(relayer.clone()))?;
+        relayers.push(relayer);
+    }
+    <RelayerThreshold>::put(n.max(1));
+    Ok(relayers)
+}
+
+/// Adds `n` proxy accounts
+fn setup_proxies<T: Trait>(n: u32) -> Result<Vec<T::AccountId>, &'static str> {
+    let mut proxies = Vec::with_capacity(n as usize);
+    for i in 0..n {
+        let proxy: T::AccountId = account("proxy", i, SEED);
+        BridgeCommon::<T>::set_proxy_accounts(RawOrigin::Root.into(), proxy.clone())?;
+        proxies.push(proxy);
+    }
+    Ok(proxies)
+}
+
+/// Votes for `call` with each of `relayers`
+fn vote_for<T: Trait>(relayers: &[T::AccountId], call: &T::Proposal) -> Result<(), &'static str> {
+    for relayer in relayers {
+        BridgeCommon::<T>::acknowledge_proposal(
+            RawOrigin::Signed(relayer.clone()).into(),
+            1,
+            SRC_ID,
+            resource(),
+            Box::new(call.clone()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Records a proposal voted by all of `relayers` whose execution failed
+fn failed_proposal<T: Trait>(relayers: &[T::AccountId], call: &T::Proposal) {
+    let hash = T::Hashing::hash_of(call);
+    let votes = ProposalVotes {
+        voted: relayers.to_vec(),
+        votes_against: vec![],
+        status: ProposalStatus::Failed,
+        expiry: system::Module::<T>::block_number() + T::ProposalLifetime::get(),
+        resource_id: resource(),
+    };
+    <Votes<T>>::insert(SRC_ID, (1, call.clone()), votes);
+    <NonceProposal<T>>::insert(SRC_ID, 1, hash);
+    <NonceVoters<T>>::insert(SRC_ID, 1, relayers.iter().map(|r| (r.clone(), hash)).collect::<Vec<_>>());
+    <FailedProposals>::insert(SRC_ID, 1, (resource(), DispatchError::Other("benchmark")));
+}
+
+/// Deposit of the remark resource attested for nonce 1 of `SRC_ID`
+fn source_deposit() -> SourceDeposit {
+    SourceDeposit { resource_id: resource(), depositor: vec![1u8; 20], amount: U256::from(1) }
+}
+
+/// Admin calls are only announced when there is an `AdminDelay`, the first one gets id 0
+fn assert_admin_action<T: Trait>(applied: bool) {
+    if T::AdminDelay::get().is_zero() {
+        assert!(applied);
+    } else {
+        assert!(BridgeCommon::<T>::admin_actions(0).is_some());
+    }
+}
+
+fn proposal_status<T: Trait>(call: &T::Proposal) -> Option<ProposalStatus> {
+    <Votes<T>>::get(SRC_ID, (1, call.clone())).map(|v| v.status)
+}
+
+benchmarks! {
+    where_clause { where T::Proposal: From<frame_system::Call<T>> }
+
+    _ { }
+
+    set_threshold {
+        setup_relayers::<T>(2)?;
+    }: _(RawOrigin::Root, 2)
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::relayer_threshold() == 2);
+    }
+
+    set_threshold_ratio {
+    }: _(RawOrigin::Root, Perbill::from_percent(67))
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::relayer_threshold_ratio() == Some(Perbill::from_percent(67)));
+    }
+
+    add_resource {
+    }: _(RawOrigin::Root, resource(), b"System.remark".to_vec())
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::resources(resource()).is_some());
+    }
+
+    remove_resource {
+        <Resources>::insert(resource(), b"System.remark".to_vec());
+    }: _(RawOrigin::Root, resource())
+    verify {
+        assert!(BridgeCommon::<T>::resources(resource()).is_none());
+    }
+
+    add_relayer {
+        let relayer = funded_account::<T>("relayer", 0);
+        let lookup = T::Lookup::unlookup(relayer.clone());
+    }: _(RawOrigin::Root, lookup)
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::is_relayer(&relayer));
+    }
+
+    remove_relayer {
+        let relayers = setup_relayers::<T>(2)?;
+        <RelayerThreshold>::put(1);
+        let lookup = T::Lookup::unlookup(relayers[0].clone());
+    }: _(RawOrigin::Root, lookup)
+    verify {
+        assert_admin_action::<T>(!BridgeCommon::<T>::is_relayer(&relayers[0]));
+    }
+
+    map_resource_and_rsymbol {
+    }: _(RawOrigin::Root, resource(), RSymbol::RFIS)
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::resource_rsymbol(resource()) == Some(RSymbol::RFIS));
+    }
+
+    unmap_resource_and_rsymbol {
+        BridgeCommon::<T>::apply_admin_action(AdminAction::MapResourceAndRsymbol(resource(), RSymbol::RFIS))?;
+    }: _(RawOrigin::Root, resource(), RSymbol::RFIS)
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::resource_rsymbol(resource()).is_none());
+    }
+
+    set_resource_decimals {
+    }: _(RawOrigin::Root, resource(), 12, 18)
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::resource_decimals(resource()) == Some((12, 18)));
+    }
+
+    // last vote of a proposal every relayer has to vote for, it executes and rewards them all
+    acknowledge_proposal {
+        let r in 1 .. T::MaxRelayers::get();
+        let relayers = setup_relayers::<T>(r)?;
+        let call = remark::<T>();
+        vote_for::<T>(&relayers[1..], &call)?;
+    }: _(RawOrigin::Signed(relayers[0].clone()), 1, SRC_ID, resource(), Box::new(call.clone()))
+    verify {
+        assert_eq!(proposal_status::<T>(&call), Some(ProposalStatus::Executed));
+    }
+
+    set_relayer_key {
+        let relayers = setup_relayers::<T>(1)?;
+        let key = RelayerKey::Sr25519(sr25519::Public::from_raw([1; 32]));
+    }: _(RawOrigin::Signed(relayers[0].clone()), key.clone())
+    verify {
+        assert_eq!(BridgeCommon::<T>::relayer_keys(&relayers[0]), Some(key));
+    }
+
+    submit_signed_proposal {
+        let s in 1 .. T::MaxRelayers::get();
+        let relayers = setup_relayers::<T>(s)?;
+        let call = remark::<T>();
+        let msg = BridgeCommon::<T>::proposal_message(SRC_ID, 1, resource(), &call);
+        let mut signatures = Vec::with_capacity(s as usize);
+        for relayer in relayers.iter() {
+            let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+            let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &msg)
+                .ok_or("failed to sign the proposal")?;
+            <RelayerKeys<T>>::insert(relayer, RelayerKey::Sr25519(public));
+            signatures.push((relayer.clone(), RelayerSignature::Sr25519(signature)));
+        }
+    }: _(RawOrigin::Signed(relayers[0].clone()), 1, SRC_ID, resource(), Box::new(call.clone()), signatures)
+    verify {
+        assert_eq!(proposal_status::<T>(&call), Some(ProposalStatus::Executed));
+    }
+
+    // first vote against a proposal every relayer has to vote for, it's rejected and the others get slashed
+    reject_proposal {
+        let r in 1 .. T::MaxRelayers::get();
+        let relayers = setup_relayers::<T>(r)?;
+        let call = remark::<T>();
+        vote_for::<T>(&relayers[1..], &call)?;
+    }: _(RawOrigin::Signed(relayers[0].clone()), 1, SRC_ID, resource(), Box::new(call.clone()))
+    verify {
+        assert_eq!(proposal_status::<T>(&call), Some(ProposalStatus::Rejected));
+    }
+
+    whitelist_chain {
+    }: _(RawOrigin::Root, SRC_ID)
+    verify {
+        assert!(BridgeCommon::<T>::chain_whitelisted(SRC_ID));
+    }
+
+    remove_whitelist_chain {
+        BridgeCommon::<T>::whitelist(SRC_ID)?;
+    }: _(RawOrigin::Root, SRC_ID)
+    verify {
+        assert!(!BridgeCommon::<T>::chain_whitelisted(SRC_ID));
+    }
+
+    set_address_format {
+        let format = AddressFormat::Bech32(b"bc".to_vec());
+    }: _(RawOrigin::Root, SRC_ID, format.clone())
+    verify {
+        assert_eq!(BridgeCommon::<T>::address_formats(SRC_ID), Some(format));
+    }
+
+    remove_address_format {
+        <AddressFormats>::insert(SRC_ID, AddressFormat::Evm);
+    }: _(RawOrigin::Root, SRC_ID)
+    verify {
+        assert_eq!(BridgeCommon::<T>::address_formats(SRC_ID), None);
+    }
+
+    set_proxy_accounts {
+        let proxy: T::AccountId = account("proxy", 0, SEED);
+    }: _(RawOrigin::Root, proxy.clone())
+    verify {
+        assert!(BridgeCommon::<T>::proxy_accounts(&proxy).is_some());
+    }
+
+    // each of the `MaxProxyAccounts` proxies submitted a schedule for `s` resources, the first one is removed
+    remove_proxy_accounts {
+        let s in 1 .. T::MaxFeeSchedules::get();
+        BridgeCommon::<T>::whitelist(SRC_ID)?;
+        let proxies = setup_proxies::<T>(T::MaxProxyAccounts::get())?;
+        for i in 0..s {
+            let resource_id = derive_resource_id(SRC_ID, &i.to_le_bytes());
+            for (j, proxy) in proxies.iter().enumerate() {
+                let schedule = FeeSchedule { flat: j as Balance, ..Default::default() };
+                BridgeCommon::<T>::submit_fee_schedule(RawOrigin::Signed(proxy.clone()).into(), SRC_ID, resource_id, schedule)?;
+            }
+        }
+    }: _(RawOrigin::Root, proxies[0].clone())
+    verify {
+        assert!(BridgeCommon::<T>::proxy_accounts(&proxies[0]).is_none());
+    }
+
+    // the `p - 1` other proxies submitted already, the median is computed over all of them
+    submit_fee_schedule {
+        let p in 1 .. T::MaxProxyAccounts::get();
+        BridgeCommon::<T>::whitelist(SRC_ID)?;
+        for (i, proxy) in setup_proxies::<T>(p - 1)?.into_iter().enumerate() {
+            let schedule = FeeSchedule { flat: i as Balance, ..Default::default() };
+            BridgeCommon::<T>::submit_fee_schedule(RawOrigin::Signed(proxy).into(), SRC_ID, resource(), schedule)?;
+        }
+        let caller: T::AccountId = whitelisted_caller();
+        BridgeCommon::<T>::set_proxy_accounts(RawOrigin::Root.into(), caller.clone())?;
+        let schedule = FeeSchedule { flat: p as Balance, ..Default::default() };
+    }: _(RawOrigin::Signed(caller), SRC_ID, resource(), schedule)
+    verify {
+        assert_eq!(BridgeCommon::<T>::fee_submissions(SRC_ID, resource()).len(), p as usize);
+    }
+
+    set_fee_currency {
+    }: _(RawOrigin::Root, SRC_ID, resource(), FeeCurrency::Asset)
+    verify {
+        assert_eq!(BridgeCommon::<T>::fee_currencies(SRC_ID, resource()), FeeCurrency::Asset);
+    }
+
+    set_fees_recipient_account {
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+    }: _(RawOrigin::Root, recipient.clone())
+    verify {
+        assert_admin_action::<T>(BridgeCommon::<T>::fees_recipient_account() == Some(recipient));
+    }
+
+    set_is_pasued {
+    }: _(RawOrigin::Root, true)
+    verify {
+        assert!(BridgeCommon::<T>::is_paused());
+    }
+
+    pause_chain {
+    }: _(RawOrigin::Root, SRC_ID)
+    verify {
+        assert!(BridgeCommon::<T>::paused_chains(SRC_ID));
+    }
+
+    unpause_chain {
+        <PausedChains>::insert(SRC_ID, true);
+    }: _(RawOrigin::Root, SRC_ID)
+    verify {
+        assert!(!BridgeCommon::<T>::paused_chains(SRC_ID));
+    }
+
+    pause_resource {
+    }: _(RawOrigin::Root, resource())
+    verify {
+        assert!(BridgeCommon::<T>::paused_resources(resource()));
+    }
+
+    unpause_resource {
+        <PausedResources>::insert(resource(), true);
+    }: _(RawOrigin::Root, resource())
+    verify {
+        assert!(!BridgeCommon::<T>::paused_resources(resource()));
+    }
+
+    claim_rewards {
+        let fees_account = funded_account::<T>("recipient", 0);
+        <FeesRecipientAccount<T>>::put(&fees_account);
+        let caller: T::AccountId = whitelisted_caller();
+        <RelayerRewards<T>>::insert(&caller, T::Currency::minimum_balance());
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert!(BridgeCommon::<T>::relayer_rewards(&caller).is_zero());
+    }
+
+    unfreeze_proposal {
+        <FrozenNonces>::insert(SRC_ID, 1, true);
+        let hash = T::Hashing::hash_of(&remark::<T>());
+    }: _(RawOrigin::Root, SRC_ID, 1, hash)
+    verify {
+        assert_admin_action::<T>(!BridgeCommon::<T>::frozen_nonces(SRC_ID, 1));
+    }
+
+    retry_proposal {
+        let r in 1 .. T::MaxRelayers::get();
+        let relayers = setup_relayers::<T>(r)?;
+        let call = remark::<T>();
+        failed_proposal::<T>(&relayers, &call);
+    }: _(RawOrigin::Root, SRC_ID, 1, Box::new(call.clone()))
+    verify {
+        assert_eq!(proposal_status::<T>(&call), Some(ProposalStatus::Executed));
+    }
+
+    attest_source_deposit {
+        let r in 1 .. T::MaxRelayers::get();
+        let relayers = setup_relayers::<T>(r)?;
+        <AddressFormats>::insert(SRC_ID, AddressFormat::Evm);
+        let deposit = source_deposit();
+        // all but the last relayer attested already
+        let hash = T::Hashing::hash_of(&deposit);
+        let attested: Vec<_> = relayers.iter().take(r as usize - 1).map(|who| (who.clone(), hash)).collect();
+        <SourceDepositVotes<T>>::insert(SRC_ID, 1, attested);
+        let last = relayers[r as usize - 1].clone();
+    }: _(RawOrigin::Signed(last), SRC_ID, 1, deposit.clone())
+    verify {
+        assert_eq!(BridgeCommon::<T>::source_deposits(SRC_ID, 1), Some(deposit));
+    }
+
+    refund_proposal {
+        let r in 1 .. T::MaxRelayers::get();
+        let relayers = setup_relayers::<T>(r)?;
+        let call = remark::<T>();
+        failed_proposal::<T>(&relayers, &call);
+        <AddressFormats>::insert(SRC_ID, AddressFormat::Evm);
+        <SourceDeposits>::insert(SRC_ID, 1, source_deposit());
+    }: _(RawOrigin::Root, SRC_ID, 1, Box::new(call.clone()))
+    verify {
+        assert_eq!(proposal_status::<T>(&call), Some(ProposalStatus::Refunded));
+    }
+
+    cancel_admin_action {
+        let at = system::Module::<T>::block_number() + One::one();
+        <AdminActions<T>>::insert(0, (at, AdminAction::SetThreshold(2)));
+        <AdminActionQueue<T>>::insert(at, vec![0]);
+    }: _(RawOrigin::Root, 0)
+    verify {
+        assert!(BridgeCommon::<T>::admin_actions(0).is_none());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+    use sp_core::{testing::KeyStore, traits::KeystoreExt};
+
+    #[test]
+    fn test_benchmarks() {
+        let mut ext = new_test_ext();
+        ext.register_extension(KeystoreExt(KeyStore::new()));
+        ext.execute_with(|| {
+            assert_ok!(test_benchmark_set_threshold::<Test>());
+            assert_ok!(test_benchmark_set_threshold_ratio::<Test>());
+            assert_ok!(test_benchmark_add_resource::<Test>());
+            assert_ok!(test_benchmark_remove_resource::<Test>());
+            assert_ok!(test_benchmark_add_relayer::<Test>());
+            assert_ok!(test_benchmark_remove_relayer::<Test>());
+            assert_ok!(test_benchmark_map_resource_and_rsymbol::<Test>());
+            assert_ok!(test_benchmark_unmap_resource_and_rsymbol::<Test>());
+            assert_ok!(test_benchmark_set_resource_decimals::<Test>());
+            assert_ok!(test_benchmark_acknowledge_proposal::<Test>());
+            assert_ok!(test_benchmark_set_relayer_key::<Test>());
+            assert_ok!(test_benchmark_submit_signed_proposal::<Test>());
+            assert_ok!(test_benchmark_reject_proposal::<Test>());
+            assert_ok!(test_benchmark_whitelist_chain::<Test>());
+            assert_ok!(test_benchmark_remove_whitelist_chain::<Test>());
+            assert_ok!(test_benchmark_set_address_format::<Test>());
+            assert_ok!(test_benchmark_remove_address_format::<Test>());
+            assert_ok!(test_benchmark_set_proxy_accounts::<Test>());
+            assert_ok!(test_benchmark_remove_proxy_accounts::<Test>());
+            assert_ok!(test_benchmark_submit_fee_schedule::<Test>());
+            assert_ok!(test_benchmark_set_fee_currency::<Test>());
+            assert_ok!(test_benchmark_set_fees_recipient_account::<Test>());
+            assert_ok!(test_benchmark_set_is_pasued::<Test>());
+            assert_ok!(test_benchmark_pause_chain::<Test>());
+            assert_ok!(test_benchmark_unpause_chain::<Test>());
+            assert_ok!(test_benchmark_pause_resource::<Test>());
+            assert_ok!(test_benchmark_unpause_resource::<Test>());
+            assert_ok!(test_benchmark_claim_rewards::<Test>());
+            assert_ok!(test_benchmark_unfreeze_proposal::<Test>());
+            assert_ok!(test_benchmark_retry_proposal::<Test>());
+            assert_ok!(test_benchmark_attest_source_deposit::<Test>());
+            assert_ok!(test_benchmark_refund_proposal::<Test>());
+            assert_ok!(test_benchmark_cancel_admin_action::<Test>());
+        });
+    }
+}
@@ -16,17 +16,32 @@ use codec::{Decode, Encode, EncodeLike};
 use frame_support::{
     Parameter, decl_error, decl_event, decl_module, decl_storage,
     dispatch::DispatchResult, ensure,
-    traits::{EnsureOrigin, Get},
-    weights::{GetDispatchInfo, Pays},
+    traits::{
+        Currency, EnsureOrigin, Filter, Get, OnUnbalanced, ReservableCurrency,
+        ExistenceRequirement::KeepAlive,
+    },
+    weights::{GetDispatchInfo, Pays, Weight},
+    storage::{with_transaction, TransactionOutcome},
 };
 
 use frame_system::{self as system, ensure_signed, ensure_root};
 use sp_core::U256;
 use sp_runtime::{
-    RuntimeDebug, ModuleId,
-    traits::{AccountIdConversion, StaticLookup, Dispatchable}
+    RuntimeDebug, ModuleId, Perbill, Permill, PerThing, DispatchError,
+    traits::{AccountIdConversion, StaticLookup, Dispatchable, One, Hash as HashT, Zero, Saturating}
 };
 use node_primitives::{ChainId, Balance, RSymbol};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+mod address;
+pub use address::AddressFormat;
+mod signature;
+pub use signature::{RelayerKey, RelayerSignature};
+pub mod default_weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -35,9 +50,16 @@ mod tests;
 
 const DEFAULT_RELAYER_THRESHOLD: u32 = 1;
 const MODULE_ID: ModuleId = ModuleId(*b"cb/bridg");
+/// Prefix of the messages relayers sign, so a signature can't be replayed as anything but a vote
+const PROPOSAL_MESSAGE_DOMAIN: &[u8] = b"bridge-common:proposal";
 
 pub type DepositNonce = u64;
 pub type ResourceId = [u8; 32];
+pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+    <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::NegativeImbalance;
+/// Identifies a proposal in `Votes`: (source chain, (nonce, call))
+pub type ProposalKey<T> = (ChainId, (DepositNonce, <T as Trait>::Proposal));
 
 /// Helper function to concatenate a chain ID and some bytes to produce a resource ID.
 /// The common format is (31 bytes unique ID + 1 byte chain ID).
@@ -52,23 +74,34 @@ pub fn derive_resource_id(chain: ChainId, id: &[u8]) -> ResourceId {
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ProposalStatus {
     Active,
-	Passed,
+    Passed,
     Expired,
     Executed,
+    Rejected,
+    /// Passed but the call failed, it can be retried or refunded
+    Failed,
+    /// Failed and the deposit was sent back to the source chain
+    Refunded,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct ProposalVotes<AccountId, BlockNumber> {
     pub voted: Vec<AccountId>,
+    pub votes_against: Vec<AccountId>,
     pub status: ProposalStatus,
     pub expiry: BlockNumber,
+    /// Resource the proposal was first voted for, its pause holds the votes back
+    pub resource_id: ResourceId,
 }
 
 impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
-    /// derivate next status according to threshold and now
-    fn derivate(&mut self, threshold: u32, now: B) -> ProposalStatus {
+    /// derivate next status according to threshold, relayer count and now
+    fn derivate(&mut self, threshold: u32, total: u32, now: B) -> ProposalStatus {
         if self.is_completed() {
             self.status.clone()
         } else if self.expiry <= now {
@@ -77,19 +110,28 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
         } else if self.voted.len() >= threshold as usize {
             self.status = ProposalStatus::Passed;
             ProposalStatus::Passed
+        } else if total >= threshold && self.votes_against.len() as u32 + threshold > total {
+            // threshold can no longer be reached by the remaining relayers
+            self.status = ProposalStatus::Rejected;
+            ProposalStatus::Rejected
         } else {
             self.status.clone()
         }
     }
 
     /// Returns true if the proposal has been rejected or approved, otherwise false.
+    /// Failed proposals no longer take votes, they are only retried or refunded.
     fn is_completed(&self) -> bool {
-        self.status == ProposalStatus::Executed || self.status == ProposalStatus::Expired
+        self.status == ProposalStatus::Executed ||
+            self.status == ProposalStatus::Expired ||
+            self.status == ProposalStatus::Rejected ||
+            self.status == ProposalStatus::Failed ||
+            self.status == ProposalStatus::Refunded
     }
 
     /// Returns true if `who` has voted for or against the proposal
     fn has_voted(&self, who: &A) -> bool {
-        self.voted.contains(&who)
+        self.voted.contains(&who) || self.votes_against.contains(&who)
     }
 
     /// Return true if the expiry time has been reached
@@ -103,32 +145,243 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
     }
 }
 
+/// Admin change to the trust assumptions of the bridge, applied `AdminDelay` blocks after it's announced
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum AdminAction<AccountId, Hash> {
+    SetThreshold(u32),
+    SetThresholdRatio(Perbill),
+    AddRelayer(AccountId),
+    RemoveRelayer(AccountId),
+    AddResource(ResourceId, Vec<u8>),
+    SetFeesRecipientAccount(AccountId),
+    MapResourceAndRsymbol(ResourceId, RSymbol),
+    UnmapResourceAndRsymbol(ResourceId, RSymbol),
+    SetResourceDecimals(ResourceId, u8, u8),
+    UnfreezeProposal(ChainId, DepositNonce, Hash),
+}
+
+/// Fee of an outbound transfer of some amount: `flat + rate * amount`, clamped to `[min, max]`
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeSchedule {
+    pub flat: Balance,
+    pub rate: Permill,
+    pub min: Balance,
+    pub max: Balance,
+}
+
+impl FeeSchedule {
+    /// Fee of a transfer of `amount`
+    pub fn fee_for(&self, amount: Balance) -> Balance {
+        self.flat.saturating_add(self.rate * amount).max(self.min).min(self.max)
+    }
+
+    /// Field by field median of `schedules`, the lower one for an even count
+    fn median(schedules: &[FeeSchedule]) -> Option<FeeSchedule> {
+        fn median_of<V: Ord + Copy>(mut values: Vec<V>) -> V {
+            values.sort();
+            values[(values.len() - 1) / 2]
+        }
+        if schedules.is_empty() {
+            return None;
+        }
+        Some(FeeSchedule {
+            flat: median_of(schedules.iter().map(|s| s.flat).collect()),
+            rate: median_of(schedules.iter().map(|s| s.rate).collect()),
+            min: median_of(schedules.iter().map(|s| s.min).collect()),
+            max: median_of(schedules.iter().map(|s| s.max).collect()),
+        })
+    }
+}
+
+/// What the fees of a resource are paid in
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum FeeCurrency {
+    /// AOW, whatever the transferred asset. Only the flat part of the fee schedule applies
+    Native,
+    /// The transferred asset itself
+    Asset,
+}
+
+impl Default for FeeCurrency {
+    fn default() -> Self {
+        FeeCurrency::Native
+    }
+}
+
+/// Payload of an outbound deposit
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum DepositData {
+    /// (amount, recipient)
+    Fungible(U256, Vec<u8>),
+    /// (token_id, recipient, metadata)
+    NonFungible(U256, Vec<u8>, Vec<u8>),
+    /// (data)
+    Generic(Vec<u8>),
+}
+
+/// Outbound deposit kept on chain, so that relayers can resync by nonce instead of replaying events
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DepositRecord<AccountId, BlockNumber> {
+    pub sender: AccountId,
+    pub resource_id: ResourceId,
+    pub data: DepositData,
+    pub block: BlockNumber,
+}
+
+/// Deposit made on the source chain for an inbound nonce, as attested by the relayers
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SourceDeposit {
+    pub resource_id: ResourceId,
+    /// Depositor on the source chain
+    pub depositor: Vec<u8>,
+    /// Amount deposited, in the units of the source chain
+    pub amount: U256,
+}
+
+/// Moves non-fungible tokens in and out of the chain for the bridge.
+pub trait NonFungibleHandler<AccountId> {
+    /// Takes `token_id` away from `who` before it leaves the chain and returns its metadata.
+    fn withdraw(who: &AccountId, resource_id: ResourceId, token_id: U256) -> Result<Vec<u8>, DispatchError>;
+    /// Hands `token_id` over to `to` once it arrived from another chain.
+    fn deposit(to: &AccountId, resource_id: ResourceId, token_id: U256, metadata: Vec<u8>) -> DispatchResult;
+}
+
+impl<AccountId> NonFungibleHandler<AccountId> for () {
+    fn withdraw(_: &AccountId, _: ResourceId, _: U256) -> Result<Vec<u8>, DispatchError> {
+        Err(DispatchError::Other("no non-fungible handler"))
+    }
+
+    fn deposit(_: &AccountId, _: ResourceId, _: U256, _: Vec<u8>) -> DispatchResult {
+        Err(DispatchError::Other("no non-fungible handler"))
+    }
+}
+
+/// Executes generic messages received from other chains.
+pub trait GenericHandler {
+    fn handle(resource_id: ResourceId, data: Vec<u8>) -> DispatchResult;
+}
+
+impl GenericHandler for () {
+    fn handle(_: ResourceId, _: Vec<u8>) -> DispatchResult {
+        Err(DispatchError::Other("no generic handler"))
+    }
+}
+
 impl<AccountId, BlockNumber: Default> Default for ProposalVotes<AccountId, BlockNumber> {
     fn default() -> Self {
         Self {
             voted: vec![],
+            votes_against: vec![],
             status: ProposalStatus::Active,
             expiry: BlockNumber::default(),
+            resource_id: ResourceId::default(),
         }
     }
 }
 
+pub trait WeightInfo {
+    fn set_threshold() -> Weight;
+    fn set_threshold_ratio() -> Weight;
+    fn add_resource() -> Weight;
+    fn remove_resource() -> Weight;
+    fn add_relayer() -> Weight;
+    fn remove_relayer() -> Weight;
+    fn map_resource_and_rsymbol() -> Weight;
+    fn unmap_resource_and_rsymbol() -> Weight;
+    fn set_resource_decimals() -> Weight;
+    fn acknowledge_proposal(r: u32, ) -> Weight;
+    fn set_relayer_key() -> Weight;
+    fn submit_signed_proposal(s: u32, ) -> Weight;
+    fn reject_proposal(r: u32, ) -> Weight;
+    fn whitelist_chain() -> Weight;
+    fn remove_whitelist_chain() -> Weight;
+    fn set_address_format() -> Weight;
+    fn remove_address_format() -> Weight;
+    fn set_proxy_accounts() -> Weight;
+    fn remove_proxy_accounts(s: u32, ) -> Weight;
+    fn submit_fee_schedule(p: u32, ) -> Weight;
+    fn set_fee_currency() -> Weight;
+    fn set_fees_recipient_account() -> Weight;
+    fn set_is_pasued() -> Weight;
+    fn pause_chain() -> Weight;
+    fn unpause_chain() -> Weight;
+    fn pause_resource() -> Weight;
+    fn unpause_resource() -> Weight;
+    fn claim_rewards() -> Weight;
+    fn unfreeze_proposal() -> Weight;
+    fn retry_proposal(r: u32, ) -> Weight;
+    fn attest_source_deposit(r: u32, ) -> Weight;
+    fn refund_proposal(r: u32, ) -> Weight;
+    fn cancel_admin_action() -> Weight;
+}
+
 pub trait Trait: system::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
     /// Origin used to administer the pallet
     type AdminOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin allowed to pause chains and resources, but not to unpause them
+    type GuardianOrigin: EnsureOrigin<Self::Origin>;
     /// Proposed dispatchable call
     type Proposal: Parameter + Dispatchable<Origin = Self::Origin> + EncodeLike + GetDispatchInfo;
+    /// Calls relayer proposals are allowed to make, they dispatch as the bridge account
+    type ProposalFilter: Filter<Self::Proposal>;
     /// The identifier for this chain.
     /// This must be unique and must not collide with existing IDs within a set of bridged chains.
     type ChainIdentity: Get<ChainId>;
 
     type ProposalLifetime: Get<Self::BlockNumber>;
+    /// Number of blocks a completed proposal is kept in `Votes` before being pruned.
+    type VotesRetention: Get<Self::BlockNumber>;
+    /// Maximum number of proposals expired or pruned in a single block.
+    type MaxSweepPerBlock: Get<u32>;
+    /// Number of most recent outbound deposits kept in `DepositRecords` for each destination chain.
+    type DepositRecordsRetention: Get<DepositNonce>;
+    /// Number of inbound nonces below the highest executed one kept in `ExecutedNonces` for each
+    /// source chain. Older nonces count as executed.
+    type ExecutedNoncesWindow: Get<DepositNonce>;
+    /// Number of proxy accounts which must submit a fee schedule before their median applies.
+    type MinFeeSubmissions: Get<u32>;
+    /// Maximum number of proxy accounts, fee schedule weights are computed for that many submissions.
+    type MaxProxyAccounts: Get<u32>;
+    /// Maximum number of destination chain and resource pairs proxy accounts submit fee schedules for.
+    type MaxFeeSchedules: Get<u32>;
+    /// Maximum number of relayers in the set, proposal weights are computed for that many voters.
+    type MaxRelayers: Get<u32>;
+    /// Number of blocks between the announcement of an `AdminAction` and its application.
+    type AdminDelay: Get<Self::BlockNumber>;
+
+    /// Currency in which relayers bond and get rewarded.
+    type Currency: ReservableCurrency<Self::AccountId>;
+    /// Amount reserved from a relayer when it joins the relayer set.
+    type RelayerBond: Get<BalanceOf<Self>>;
+    /// Collected fees shared among the relayers who voted for an executed proposal.
+    type RelayerReward: Get<BalanceOf<Self>>;
+    /// Amount slashed from the bond of a relayer which voted for a conflicting
+    /// proposal or for one that never reached the threshold.
+    type RelayerSlash: Get<BalanceOf<Self>>;
+    /// Handler for the slashed bonds.
+    type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+    /// Weight information for the extrinsics of this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_event! {
     pub enum Event<T> where
-        AccountId = <T as system::Trait>::AccountId 
+        AccountId = <T as system::Trait>::AccountId,
+        Hash = <T as system::Trait>::Hash,
+        BlockNumber = <T as system::Trait>::BlockNumber,
+        BondBalance = BalanceOf<T>,
+        AdminAction = AdminAction<<T as system::Trait>::AccountId, <T as system::Trait>::Hash>,
     {
         /// Vote threshold has changed (new_threshold)
         RelayerThresholdChanged(u32),
@@ -136,14 +389,28 @@ decl_event! {
         RelayerAdded(AccountId),
         /// Relayer removed from set
         RelayerRemoved(AccountId),
+        /// Relayer registered the key it signs proposals with
+        RelayerKeySet(AccountId),
         /// Chain now available for transfers (chain_id)
         ChainWhitelisted(ChainId),
         /// Chain now unavailable
         ChainRemoved(ChainId),
+        /// Recipients sent to a chain must now have this format
+        AddressFormatSet(ChainId, AddressFormat),
+        /// Address format of a chain is no longer known
+        AddressFormatRemoved(ChainId),
         /// FunglibleTransfer is for relaying fungibles (AccountId, dest_id, nonce, resource_id, amount, recipient, metadata)
         FungibleTransfer(AccountId, ChainId, DepositNonce, ResourceId, U256, Vec<u8>),
-        /// Set Chain fees
-        ChainFeesSet(ChainId, Balance),
+        /// NonFungibleTransfer is for relaying NFTs (AccountId, dest_id, nonce, resource_id, token_id, recipient, metadata)
+        NonFungibleTransfer(AccountId, ChainId, DepositNonce, ResourceId, U256, Vec<u8>, Vec<u8>),
+        /// GenericTransfer is for relaying arbitrary data to a contract (AccountId, dest_id, nonce, resource_id, data)
+        GenericTransfer(AccountId, ChainId, DepositNonce, ResourceId, Vec<u8>),
+        /// A proxy account submitted a fee schedule (proxy, dest_id, resource_id, schedule)
+        FeeScheduleSubmitted(AccountId, ChainId, ResourceId, FeeSchedule),
+        /// Fee schedule applied to transfers of a resource towards a chain changed
+        FeeScheduleSet(ChainId, ResourceId, FeeSchedule),
+        /// Fees of transfers of a resource towards a chain are now paid in this currency
+        FeeCurrencySet(ChainId, ResourceId, FeeCurrency),
         /// Vote submitted in favour of proposal
         VoteFor(ChainId, DepositNonce, AccountId),
         /// Vot submitted against proposal
@@ -154,6 +421,38 @@ decl_event! {
         ProposalCancelled(ChainId, DepositNonce),
         /// Execution of call succeeded
         ProposalExecuted(ChainId, DepositNonce),
+        /// Execution of call failed, the proposal waits for a retry or a refund
+        ProposalFailed(ChainId, DepositNonce, DispatchError),
+        /// Deposit of a failed proposal was sent back to the source chain
+        ProposalRefunded(ChainId, DepositNonce),
+        /// Relayers agreed on the source-side deposit of an inbound nonce, it can be refunded
+        SourceDepositAttested(ChainId, DepositNonce),
+        /// A second, different proposal was submitted for a nonce (chain_id, nonce, existing call hash, conflicting call hash)
+        ProposalConflict(ChainId, DepositNonce, Hash, Hash),
+        /// Proposals for a frozen nonce can be voted again (chain_id, nonce, accepted call hash)
+        ProposalUnfrozen(ChainId, DepositNonce, Hash),
+        /// Relayer bond has been slashed (relayer, amount)
+        RelayerSlashed(AccountId, BondBalance),
+        /// Relayer claimed its share of the fees (relayer, amount)
+        RelayerRewarded(AccountId, BondBalance),
+        /// Transfers from and to a chain are suspended
+        ChainPaused(ChainId),
+        /// Transfers from and to a chain are allowed again
+        ChainUnpaused(ChainId),
+        /// Transfers of a resource are suspended
+        ResourcePaused(ResourceId),
+        /// Transfers of a resource are allowed again
+        ResourceUnpaused(ResourceId),
+        /// Decimals of a resource on this chain and on the other chains (resource_id, local, foreign)
+        ResourceDecimalsSet(ResourceId, u8, u8),
+        /// Admin change announced, it applies at the given block unless cancelled (id, action, block)
+        AdminActionAnnounced(u32, AdminAction, BlockNumber),
+        /// Announced admin change cancelled
+        AdminActionCancelled(u32),
+        /// Announced admin change applied
+        AdminActionApplied(u32),
+        /// Announced admin change could no longer be applied once its delay passed
+        AdminActionFailed(u32, DispatchError),
     }
 }
 
@@ -171,18 +470,38 @@ decl_error! {
         ChainAlreadyWhitelisted,
         /// Provided proxy account is not valid
         InvalidProxyAccount,
+        /// There are already `MaxProxyAccounts` proxy accounts
+        TooManyProxyAccounts,
+        /// Fee schedules are already submitted for `MaxFeeSchedules` chain and resource pairs
+        TooManyFeeSchedules,
+        /// Minimum fee of the schedule is above its maximum
+        InvalidFeeSchedule,
         /// Resource ID provided isn't mapped to anything
         ResourceDoesNotExist,
         /// Relayer already in set
         RelayerAlreadyExists,
+        /// Relayer set already has `MaxRelayers` members
+        TooManyRelayers,
+        /// Removing the relayer would leave fewer relayers than the threshold
+        ThresholdUnreachable,
         /// Provided accountId is not a relayer
         RelayerInvalid,
         /// Protected operation, must be performed by relayer
         MustBeRelayer,
         /// Relayer has already submitted some vote for this proposal
         RelayerAlreadyVoted,
+        /// Relayer hasn't registered a signing key
+        RelayerKeyNotSet,
+        /// Signature doesn't match the proposal or the key of the relayer
+        InvalidSignature,
+        /// Relayer signed the proposal more than once
+        DuplicateSignature,
+        /// Not enough relayers signed the proposal to reach the threshold
+        NotEnoughSignatures,
         /// A proposal with these parameters has already been submitted
         ProposalAlreadyExists,
+        /// Relayer proposals can't make this call
+        ProposalNotAllowed,
         /// No proposal with the ID was found
         ProposalDoesNotExist,
         /// Cannot complete proposal, needs more votes
@@ -191,6 +510,38 @@ decl_error! {
         ProposalAlreadyCompleted,
         /// Lifetime of proposal has been exceeded
         ProposalExpired,
+        /// A proposal with this nonce has already been executed
+        NonceAlreadyExecuted,
+        /// Conflicting proposals exist for this nonce, voting is frozen
+        ProposalFrozen,
+        /// Nonce is not frozen
+        ProposalNotFrozen,
+        /// Proposal didn't fail, there is nothing to retry or refund
+        ProposalNotFailed,
+        /// Relayers haven't attested the source-side deposit of this nonce
+        SourceDepositUnknown,
+        /// Source-side deposit of this nonce is already attested
+        SourceDepositAlreadyAttested,
+        /// Attested source-side deposit is of another resource
+        SourceDepositMismatch,
+        /// Relayer has no reward to claim
+        NoRewards,
+        /// Fees recipient account is not set
+        FeesRecipientAccountNotSet,
+        /// Transfers from and to this chain are paused
+        ChainPaused,
+        /// Transfers of this resource are paused
+        ResourcePaused,
+        /// No address format is registered for the destination chain
+        AddressFormatNotSet,
+        /// Recipient doesn't match the address format of the destination chain
+        InvalidRecipient,
+        /// Amount has more precision than the other side can represent
+        AmountNotRepresentable,
+        /// Amount overflows once scaled to the decimals of the other side
+        AmountOverflow,
+        /// No pending admin action with this id
+        AdminActionNotFound,
     }
 }
 
@@ -199,20 +550,48 @@ decl_storage! {
         /// All whitelisted chains and their respective transaction counts
         pub ChainNonces get(fn chains): map hasher(twox_64_concat) ChainId => Option<DepositNonce>;
 
-        /// fee to cover the commission happened on other chains such as ethereum
-        pub ChainFees get(fn chain_fees): map hasher(twox_64_concat) ChainId => Option<Balance>;
+        /// Format of the recipient addresses of each destination chain
+        pub AddressFormats get(fn address_formats) config(): map hasher(twox_64_concat) ChainId => Option<AddressFormat>;
+
+        /// Fee schedule of the transfers of each resource towards each chain, to cover
+        /// the commission happened on other chains such as ethereum
+        pub FeeSchedules get(fn fee_schedules) config():
+            double_map hasher(twox_64_concat) ChainId, hasher(blake2_128_concat) ResourceId => Option<FeeSchedule>;
+
+        /// Latest fee schedule submitted by each proxy account
+        pub FeeSubmissions get(fn fee_submissions):
+            double_map hasher(twox_64_concat) ChainId, hasher(blake2_128_concat) ResourceId => Vec<(T::AccountId, FeeSchedule)>;
+
+        /// Currency the fees of the transfers of each resource towards each chain are paid in
+        pub FeeCurrencies get(fn fee_currencies):
+            double_map hasher(twox_64_concat) ChainId, hasher(blake2_128_concat) ResourceId => FeeCurrency;
+
+        /// Number of chain and resource pairs in `FeeSubmissions`
+        pub FeeSubmissionCount get(fn fee_submission_count): u32;
 
         /// Proxy accounts for setting chain fees
         ProxyAccounts get(fn proxy_accounts): map hasher(twox_64_concat) T::AccountId => Option<u8>;
 
+        /// Number of proxy accounts
+        pub ProxyAccountCount get(fn proxy_account_count): u32;
+
         /// Recipient account for fees
-        FeesRecipientAccount get(fn fees_recipient_account): Option<T::AccountId>;
+        pub FeesRecipientAccount get(fn fees_recipient_account): Option<T::AccountId>;
 
         /// True if the bridge is paused.
         pub IsPaused get(fn is_paused): bool = false;
+
+        /// Chains whose transfers, outbound and inbound, are paused
+        pub PausedChains get(fn paused_chains): map hasher(twox_64_concat) ChainId => bool;
+
+        /// Resources whose transfers, outbound and inbound, are paused
+        pub PausedResources get(fn paused_resources): map hasher(blake2_128_concat) ResourceId => bool;
         
         /// Number of votes required for a proposal to execute
-        RelayerThreshold get(fn relayer_threshold): u32 = DEFAULT_RELAYER_THRESHOLD;
+        RelayerThreshold get(fn relayer_threshold) config(): u32 = DEFAULT_RELAYER_THRESHOLD;
+
+        /// If set, the threshold is this ratio of the relayer count (rounded up) instead of `RelayerThreshold`
+        pub RelayerThresholdRatio get(fn relayer_threshold_ratio): Option<Perbill>;
 
         /// Tracks current relayer set
         pub Relayers get(fn relayers): map hasher(blake2_128_concat) T::AccountId => bool;
@@ -220,19 +599,107 @@ decl_storage! {
         /// Number of relayers in set
         pub RelayerCount get(fn relayer_count): u32;
 
+        /// Key each relayer signs proposals with
+        pub RelayerKeys get(fn relayer_keys): map hasher(blake2_128_concat) T::AccountId => Option<RelayerKey>;
+
+        /// Amount currently bonded by each relayer
+        pub RelayerBonds get(fn relayer_bonds): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+        /// Fees earned by relayers and not claimed yet
+        pub RelayerRewards get(fn relayer_rewards): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
         /// All known proposals.
         /// The key is the hash of the call and the deposit ID, to ensure it's unique.
         pub Votes get(fn votes):
             double_map hasher(blake2_128_concat) ChainId, hasher(blake2_128_concat) (DepositNonce, T::Proposal)
             => Option<ProposalVotes<T::AccountId, T::BlockNumber>>;
 
+        /// Hash of the call first proposed for an inbound (chain_id, nonce)
+        pub NonceProposal get(fn nonce_proposal):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => Option<T::Hash>;
+
+        /// Inbound nonces with conflicting proposals, no vote is accepted until an admin unfreezes them
+        pub FrozenNonces get(fn frozen_nonces):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => bool;
+
+        /// Relayers which voted for an inbound nonce, along with the hash of the call they voted for
+        pub NonceVoters get(fn nonce_voters):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => Vec<(T::AccountId, T::Hash)>;
+
+        /// Executed inbound nonces and the hash of the executed call
+        pub ExecutedNonces get(fn executed_nonces):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => Option<T::Hash>;
+
+        /// Highest executed inbound nonce of each source chain
+        pub ExecutedNoncesMark get(fn executed_nonces_mark): map hasher(twox_64_concat) ChainId => DepositNonce;
+
+        /// Resource and error of the last execution of each failed inbound nonce
+        pub FailedProposals get(fn failed_proposals):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => Option<(ResourceId, DispatchError)>;
+
+        /// Inbound nonce whose call is being dispatched, for the handlers which need to know where it came from
+        pub ExecutingNonce get(fn executing_nonce): Option<(ChainId, DepositNonce)>;
+
+        /// Latest outbound deposits, at most `DepositRecordsRetention` of them for each destination chain
+        pub DepositRecords get(fn deposit_records):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce
+            => Option<DepositRecord<T::AccountId, T::BlockNumber>>;
+
+        /// Source-side deposits of inbound nonces, attested by the relayers so that they can be refunded
+        pub SourceDeposits get(fn source_deposits):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => Option<SourceDeposit>;
+
+        /// Relayers which attested a source-side deposit not agreed on yet, along with the hash of what they attested
+        pub SourceDepositVotes get(fn source_deposit_votes):
+            double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => Vec<(T::AccountId, T::Hash)>;
+
+        /// Proposals to be marked as expired at a block, if still active by then
+        pub ExpiryQueue get(fn expiry_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<ProposalKey<T>>;
+
+        /// Completed proposals to be removed from `Votes` at a block
+        pub PruneQueue get(fn prune_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<ProposalKey<T>>;
+
+        /// Announced admin actions and the block they apply at
+        pub AdminActions get(fn admin_actions):
+            map hasher(twox_64_concat) u32 => Option<(T::BlockNumber, AdminAction<T::AccountId, T::Hash>)>;
+
+        /// Id of the next announced admin action
+        pub NextAdminActionId get(fn next_admin_action_id): u32;
+
+        /// Ids of the admin actions applying at a block
+        pub AdminActionQueue get(fn admin_action_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<u32>;
+
         /// Utilized by the bridge software to map resource IDs to actual methods
-        pub Resources get(fn resources): map hasher(blake2_128_concat) ResourceId => Option<Vec<u8>>;
+        pub Resources get(fn resources) config(): map hasher(blake2_128_concat) ResourceId => Option<Vec<u8>>;
 
         /// rId => Rsymbol
         pub ResourceRsymbol get(fn resource_rsymbol): map hasher(blake2_128_concat) ResourceId => Option<RSymbol>;
         /// Rsymbol => ResourceId
         pub RsymbolResource get(fn rsymbol_resource): map hasher(blake2_128_concat) RSymbol => Option<ResourceId>;
+
+        /// Decimals of each resource (on this chain, on the other chains), amounts are taken as is when unset
+        pub ResourceDecimals get(fn resource_decimals) config(): map hasher(blake2_128_concat) ResourceId => Option<(u8, u8)>;
+    }
+    add_extra_genesis {
+        // initial relayers, they are not bonded
+        config(relayers): Vec<T::AccountId>;
+        // chains whitelisted from genesis
+        config(chains): Vec<ChainId>;
+        config(fees_recipient): Option<T::AccountId>;
+        build(|config| {
+            for relayer in &config.relayers {
+                if !<Relayers<T>>::contains_key(relayer) {
+                    <Relayers<T>>::insert(relayer, true);
+                    <RelayerCount>::mutate(|i| *i += 1);
+                }
+            }
+            for chain in &config.chains {
+                <ChainNonces>::insert(chain, 0);
+            }
+            if let Some(ref who) = config.fees_recipient {
+                <FeesRecipientAccount<T>>::put(who);
+            }
+        });
     }
 }
 
@@ -243,36 +710,62 @@ decl_module! {
         const ChainIdentity: ChainId = T::ChainIdentity::get();
         const BridgeAccountId: T::AccountId = MODULE_ID.into_account();
         const ProposalLifetime: T::BlockNumber = T::ProposalLifetime::get();
+        const VotesRetention: T::BlockNumber = T::VotesRetention::get();
+        const MaxSweepPerBlock: u32 = T::MaxSweepPerBlock::get();
+        const DepositRecordsRetention: DepositNonce = T::DepositRecordsRetention::get();
+        const ExecutedNoncesWindow: DepositNonce = T::ExecutedNoncesWindow::get();
+        const MinFeeSubmissions: u32 = T::MinFeeSubmissions::get();
+        const MaxProxyAccounts: u32 = T::MaxProxyAccounts::get();
+        const MaxFeeSchedules: u32 = T::MaxFeeSchedules::get();
+        const MaxRelayers: u32 = T::MaxRelayers::get();
+        const AdminDelay: T::BlockNumber = T::AdminDelay::get();
+        const RelayerBond: BalanceOf<T> = T::RelayerBond::get();
+        const RelayerReward: BalanceOf<T> = T::RelayerReward::get();
+        const RelayerSlash: BalanceOf<T> = T::RelayerSlash::get();
 
         fn deposit_event() = default;
 
-        /// Sets the vote threshold for proposals.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::sweep_proposals(now).saturating_add(Self::apply_due_admin_actions(now))
+        }
+
+        /// Sets the vote threshold for proposals, once `AdminDelay` has passed.
         ///
         /// This threshold is used to determine how many votes are required
-        /// before a proposal is executed.
+        /// before a proposal is executed. It can't exceed the number of relayers.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 10_000]
+        #[weight = T::WeightInfo::set_threshold()]
         pub fn set_threshold(origin, threshold: u32) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            ensure!(threshold > 0, Error::<T>::InvalidThreshold);
-            <RelayerThreshold>::put(threshold);
-            Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
-            Ok(())
+            Self::announce_admin_action(AdminAction::SetThreshold(threshold))
         }
 
-        /// Stores a method name on chain under an associated resource ID.
+        /// Sets the vote threshold as a ratio of the relayer set size, once `AdminDelay` has passed.
+        ///
+        /// The required number of votes then follows `RelayerCount`, until an
+        /// absolute threshold is set again with `set_threshold`.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = T::WeightInfo::set_threshold_ratio()]
+        pub fn set_threshold_ratio(origin, ratio: Perbill) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::announce_admin_action(AdminAction::SetThresholdRatio(ratio))
+        }
+
+        /// Stores a method name on chain under an associated resource ID, once `AdminDelay` has passed.
         ///
         /// # <weight>
         /// - O(1) write
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::add_resource()]
         pub fn add_resource(origin, id: ResourceId, method: Vec<u8>) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            <Resources>::insert(id, method);
-            Ok(())
+            Self::announce_admin_action(AdminAction::AddResource(id, method))
         }
 
         /// Removes a resource ID from the resource mapping.
@@ -283,71 +776,79 @@ decl_module! {
         /// # <weight>
         /// - O(1) removal
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::remove_resource()]
         pub fn remove_resource(origin, id: ResourceId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             <Resources>::remove(id);
             Ok(())
         }
 
-        /// Adds a new relayer to the relayer set.
+        /// Adds a new relayer to the relayer set, once `AdminDelay` has passed.
+        ///
+        /// `RelayerBond` is reserved from the relayer's account.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 10_000]
+        #[weight = T::WeightInfo::add_relayer()]
         pub fn add_relayer(origin, who: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
             Self::ensure_admin(origin)?;
             let relayer = T::Lookup::lookup(who)?;
-            ensure!(!Self::is_relayer(&relayer), Error::<T>::RelayerAlreadyExists);
-
-            <Relayers<T>>::insert(&relayer, true);
-            <RelayerCount>::mutate(|i| *i += 1);
-    
-            Self::deposit_event(RawEvent::RelayerAdded(relayer));
-            Ok(())
+            Self::announce_admin_action(AdminAction::AddRelayer(relayer))
         }
 
-        /// Removes an existing relayer from the set.
+        /// Removes an existing relayer from the set and releases what is left of its bond,
+        /// once `AdminDelay` has passed.
         ///
         /// # <weight>
         /// - O(1) lookup and removal
         /// # </weight>
-        #[weight = 10_000]
+        #[weight = T::WeightInfo::remove_relayer()]
         pub fn remove_relayer(origin, dest: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
             Self::ensure_admin(origin)?;
             let relayer = T::Lookup::lookup(dest)?;
-            ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
-
-            <Relayers<T>>::remove(&relayer);
-            <RelayerCount>::mutate(|i| *i -= 1);
-
-            Self::deposit_event(RawEvent::RelayerRemoved(relayer));
-            Ok(())
+            Self::announce_admin_action(AdminAction::RemoveRelayer(relayer))
         }
 
-        /// Map resourceId to Rsymbol
-        #[weight = 10_000]
+        /// Map resourceId to Rsymbol, once `AdminDelay` has passed
+        #[weight = T::WeightInfo::map_resource_and_rsymbol()]
         pub fn map_resource_and_rsymbol(origin, resource_id: ResourceId, sym: RSymbol) -> DispatchResult {
             Self::ensure_admin(origin)?;
-
-            <ResourceRsymbol>::insert(&resource_id, &sym);
-            <RsymbolResource>::insert(&sym, &resource_id);
-
-            Ok(())
+            Self::announce_admin_action(AdminAction::MapResourceAndRsymbol(resource_id, sym))
         }
 
-        /// Unmap resourceId to Rsymbol
-        #[weight = 10_000]
+        /// Unmap resourceId to Rsymbol, once `AdminDelay` has passed
+        #[weight = T::WeightInfo::unmap_resource_and_rsymbol()]
         pub fn unmap_resource_and_rsymbol(origin, resource_id: ResourceId, sym: RSymbol) -> DispatchResult {
             Self::ensure_admin(origin)?;
+            Self::announce_admin_action(AdminAction::UnmapResourceAndRsymbol(resource_id, sym))
+        }
 
-            <ResourceRsymbol>::remove(&resource_id);
-            <RsymbolResource>::remove(&sym);
+        /// Cancels an announced admin action before it applies.
+        ///
+        /// Only the admin may cancel, a single guardian can't hold back the changes of the admin.
+        ///
+        /// # <weight>
+        /// - O(A) where A is the number of actions applying in the same block
+        /// # </weight>
+        #[weight = T::WeightInfo::cancel_admin_action()]
+        pub fn cancel_admin_action(origin, id: u32) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            let (at, _) = <AdminActions<T>>::take(id).ok_or(Error::<T>::AdminActionNotFound)?;
+            <AdminActionQueue<T>>::mutate(at, |ids| ids.retain(|i| *i != id));
 
+            Self::deposit_event(RawEvent::AdminActionCancelled(id));
             Ok(())
         }
 
+        /// Sets the decimals of a resource on this chain and on the other chains, once `AdminDelay` has passed.
+        /// Amounts of the resource crossing the bridge are scaled accordingly.
+        #[weight = T::WeightInfo::set_resource_decimals()]
+        pub fn set_resource_decimals(origin, resource_id: ResourceId, local: u8, foreign: u8) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::announce_admin_action(AdminAction::SetResourceDecimals(resource_id, local, foreign))
+        }
+
         /// Commits a vote in favour of the provided proposal.
         ///
         /// If a proposal with the given nonce and source chain ID does not already exist, it will
@@ -355,16 +856,108 @@ decl_module! {
         ///
         /// # <weight>
         /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(R) rewards once executed, R being at most `MaxRelayers`
         /// # </weight>
-        #[weight = (call.get_dispatch_info().weight + 195_000_000, call.get_dispatch_info().class, Pays::Yes)]
+        #[weight = (
+            call.get_dispatch_info().weight.saturating_add(T::WeightInfo::acknowledge_proposal(T::MaxRelayers::get())),
+            call.get_dispatch_info().class,
+            Pays::Yes,
+        )]
         pub fn acknowledge_proposal(origin, nonce: DepositNonce, src_id: ChainId, resource_id: ResourceId, call: Box<T::Proposal>) -> DispatchResult {
             let who = ensure_signed(origin)?;
             ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
             ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
             ensure!(Self::resources(resource_id).is_some(), Error::<T>::ResourceDoesNotExist);
+            ensure!(T::ProposalFilter::filter(&call), Error::<T>::ProposalNotAllowed);
+            Self::ensure_not_paused(src_id, resource_id)?;
 
-            Self::commit_vote(who, nonce, src_id, call.clone())?;
-            Self::try_resolve_proposal(nonce, src_id, call)
+            Self::commit_vote(who, nonce, src_id, resource_id, call.clone(), true)?;
+            Self::try_resolve_proposal(nonce, src_id, resource_id, call)
+        }
+
+        /// Registers the key the caller signs proposals with, for `submit_signed_proposal`.
+        #[weight = T::WeightInfo::set_relayer_key()]
+        pub fn set_relayer_key(origin, key: RelayerKey) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            <RelayerKeys<T>>::insert(&who, key);
+
+            Self::deposit_event(RawEvent::RelayerKeySet(who));
+            Ok(())
+        }
+
+        /// Commits the votes of several relayers at once, from their signatures over
+        /// `proposal_message`, and executes the proposal if the threshold is met.
+        ///
+        /// Signers that already voted on the proposal are skipped.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(S) signature verifications and votes, S being the number of signatures
+        /// # </weight>
+        #[weight = (
+            call.get_dispatch_info().weight.saturating_add(T::WeightInfo::submit_signed_proposal(signatures.len() as u32)),
+            call.get_dispatch_info().class,
+            Pays::Yes,
+        )]
+        pub fn submit_signed_proposal(
+            origin,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            resource_id: ResourceId,
+            call: Box<T::Proposal>,
+            signatures: Vec<(T::AccountId, RelayerSignature)>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
+            ensure!(Self::resources(resource_id).is_some(), Error::<T>::ResourceDoesNotExist);
+            ensure!(T::ProposalFilter::filter(&call), Error::<T>::ProposalNotAllowed);
+            Self::ensure_not_paused(src_id, resource_id)?;
+
+            // check every signature before any vote is written
+            let msg = Self::proposal_message(src_id, nonce, resource_id, &call);
+            let mut signers: Vec<T::AccountId> = Vec::with_capacity(signatures.len());
+            for (signer, signature) in signatures.iter() {
+                ensure!(Self::is_relayer(signer), Error::<T>::RelayerInvalid);
+                ensure!(!signers.contains(signer), Error::<T>::DuplicateSignature);
+                let key = Self::relayer_keys(signer).ok_or(Error::<T>::RelayerKeyNotSet)?;
+                ensure!(key.verify(&msg, signature), Error::<T>::InvalidSignature);
+                signers.push(signer.clone());
+            }
+
+            let votes = <Votes<T>>::get(src_id, (nonce, call.clone())).unwrap_or_default();
+            ensure!(!votes.is_completed(), Error::<T>::ProposalAlreadyCompleted);
+            let new_votes = signers.iter().filter(|s| !votes.has_voted(s)).count();
+            ensure!(
+                votes.voted.len() + new_votes >= Self::effective_threshold() as usize,
+                Error::<T>::NotEnoughSignatures
+            );
+
+            for signer in signers.into_iter().filter(|s| !votes.has_voted(s)) {
+                Self::commit_vote(signer, nonce, src_id, resource_id, call.clone(), true)?;
+            }
+            Self::try_resolve_proposal(nonce, src_id, resource_id, call)
+        }
+
+        /// Commits a vote against a provided proposal.
+        ///
+        /// Once enough relayers voted against it that the threshold can no longer be
+        /// reached, the proposal is rejected and can't be executed anymore.
+        ///
+        /// # <weight>
+        /// - Fixed, since execution of proposal should not be included
+        /// - O(R) slashes once rejected, R being at most `MaxRelayers`
+        /// # </weight>
+        #[weight = T::WeightInfo::reject_proposal(T::MaxRelayers::get())]
+        pub fn reject_proposal(origin, nonce: DepositNonce, src_id: ChainId, resource_id: ResourceId, call: Box<T::Proposal>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
+            ensure!(Self::resources(resource_id).is_some(), Error::<T>::ResourceDoesNotExist);
+
+            Self::commit_vote(who, nonce, src_id, resource_id, call.clone(), false)?;
+            Self::try_resolve_proposal(nonce, src_id, resource_id, call)
         }
 
         /// Enables a chain ID as a source or destination for a bridge transfer.
@@ -372,7 +965,7 @@ decl_module! {
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::whitelist_chain()]
         pub fn whitelist_chain(origin, id: ChainId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::whitelist(id)
@@ -383,7 +976,7 @@ decl_module! {
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::remove_whitelist_chain()]
         pub fn remove_whitelist_chain(origin, id: ChainId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             ensure!(Self::chain_whitelisted(id), Error::<T>::ChainNotWhitelisted);
@@ -393,70 +986,145 @@ decl_module! {
             Ok(())
         }
 
-        /// Set proxy accounts.
+        /// Sets the format recipients sent to a chain must have.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = T::WeightInfo::set_address_format()]
+        pub fn set_address_format(origin, id: ChainId, format: AddressFormat) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            <AddressFormats>::insert(id, format.clone());
+
+            Self::deposit_event(RawEvent::AddressFormatSet(id, format));
+            Ok(())
+        }
+
+        /// Forgets the address format of a chain, outbound transfers to it are refused until a new one is set.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = T::WeightInfo::remove_address_format()]
+        pub fn remove_address_format(origin, id: ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            ensure!(<AddressFormats>::contains_key(id), Error::<T>::AddressFormatNotSet);
+            <AddressFormats>::remove(id);
+
+            Self::deposit_event(RawEvent::AddressFormatRemoved(id));
+            Ok(())
+        }
+
+        /// Set proxy accounts, at most `MaxProxyAccounts` of them.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 100_000_000]
+        #[weight = T::WeightInfo::set_proxy_accounts()]
         pub fn set_proxy_accounts(origin, account: T::AccountId) -> DispatchResult {
             Self::ensure_admin(origin)?;
+            if !<ProxyAccounts<T>>::contains_key(&account) {
+                ensure!(Self::proxy_account_count() < T::MaxProxyAccounts::get(), Error::<T>::TooManyProxyAccounts);
+                <ProxyAccountCount>::mutate(|i| *i += 1);
+            }
             <ProxyAccounts<T>>::insert(account, 0);
 
             Ok(())
         }
 
-        /// Remove proxy accounts.
+        /// Remove proxy accounts, along with the fee schedules they submitted.
         ///
         /// # <weight>
-        /// - O(1) lookup and insert
+        /// - O(S * P) where S is the number of chain and resource pairs with fee schedules submitted,
+        ///   at most `MaxFeeSchedules`, and P the number of proxy accounts
         /// # </weight>
-        #[weight = 100_000_000]
+        #[weight = T::WeightInfo::remove_proxy_accounts(T::MaxFeeSchedules::get())]
         pub fn remove_proxy_accounts(origin, account: T::AccountId) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            <ProxyAccounts<T>>::remove(account);
+            if <ProxyAccounts<T>>::take(&account).is_some() {
+                <ProxyAccountCount>::mutate(|i| *i = i.saturating_sub(1));
+            }
+
+            let submitted: Vec<(ChainId, ResourceId)> = <FeeSubmissions<T>>::iter()
+                .filter(|(_, _, subs)| subs.iter().any(|(who, _)| who == &account))
+                .map(|(id, resource_id, _)| (id, resource_id))
+                .collect();
+            for (id, resource_id) in submitted {
+                let emptied = <FeeSubmissions<T>>::mutate(id, resource_id, |subs| {
+                    subs.retain(|(who, _)| who != &account);
+                    subs.is_empty()
+                });
+                if emptied {
+                    <FeeSubmissions<T>>::remove(id, resource_id);
+                    <FeeSubmissionCount>::mutate(|i| *i = i.saturating_sub(1));
+                }
+                Self::update_fee_schedule(id, resource_id);
+            }
 
             Ok(())
         }
 
-        /// Set fees for a chain ID.
+        /// Submits the fee schedule a proxy account quotes for transfers of `resource_id` towards `id`.
+        ///
+        /// The applied schedule is the median of the latest submission of each proxy account,
+        /// once at least `MinFeeSubmissions` of them submitted one.
         ///
         /// # <weight>
-        /// - O(1) lookup and insert
+        /// - O(P) where P is the number of proxy accounts, at most `MaxProxyAccounts`
         /// # </weight>
-        #[weight = 100_000_000]
-        pub fn set_chain_fees(origin, id: ChainId, fees: Balance) -> DispatchResult {
+        #[weight = T::WeightInfo::submit_fee_schedule(T::MaxProxyAccounts::get())]
+        pub fn submit_fee_schedule(origin, id: ChainId, resource_id: ResourceId, schedule: FeeSchedule) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             ensure!(Self::chain_whitelisted(id), Error::<T>::InvalidChainId);
             ensure!(<ProxyAccounts<T>>::contains_key(&who), Error::<T>::InvalidProxyAccount);
+            ensure!(schedule.min <= schedule.max, Error::<T>::InvalidFeeSchedule);
+            if !<FeeSubmissions<T>>::contains_key(id, resource_id) {
+                ensure!(Self::fee_submission_count() < T::MaxFeeSchedules::get(), Error::<T>::TooManyFeeSchedules);
+                <FeeSubmissionCount>::mutate(|i| *i += 1);
+            }
 
-            <ChainFees>::insert(id, fees);
+            <FeeSubmissions<T>>::mutate(id, resource_id, |subs| {
+                subs.retain(|(proxy, _)| proxy != &who);
+                subs.push((who.clone(), schedule));
+            });
 
-            Self::deposit_event(RawEvent::ChainFeesSet(id, fees));
+            Self::deposit_event(RawEvent::FeeScheduleSubmitted(who, id, resource_id, schedule));
+            Self::update_fee_schedule(id, resource_id);
             Ok(())
         }
 
-        /// Set fees recipient account.
+        /// Sets whether the fees of transfers of `resource_id` towards `id` are paid in AOW or in the asset itself.
         ///
         /// # <weight>
-        /// - O(1) lookup and insert
+        /// - O(1) write
         /// # </weight>
-        #[weight = 100_000_000]
-        pub fn set_fees_recipient_account(origin, account: T::AccountId) -> DispatchResult {
+        #[weight = T::WeightInfo::set_fee_currency()]
+        pub fn set_fee_currency(origin, id: ChainId, resource_id: ResourceId, currency: FeeCurrency) -> DispatchResult {
             Self::ensure_admin(origin)?;
+            <FeeCurrencies>::insert(id, resource_id, currency);
 
-            <FeesRecipientAccount<T>>::put(account);
-
+            Self::deposit_event(RawEvent::FeeCurrencySet(id, resource_id, currency));
             Ok(())
         }
 
+        /// Set fees recipient account, once `AdminDelay` has passed.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = T::WeightInfo::set_fees_recipient_account()]
+        pub fn set_fees_recipient_account(origin, account: T::AccountId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::announce_admin_action(AdminAction::SetFeesRecipientAccount(account))
+        }
+
         /// Set whether to pause.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 100_000_000]
+        #[weight = T::WeightInfo::set_is_pasued()]
         pub fn set_is_pasued(origin, is_paused: bool) -> DispatchResult {
             Self::ensure_admin(origin)?;
 
@@ -464,6 +1132,181 @@ decl_module! {
 
             Ok(())
         }
+
+        /// Pauses transfers from and to a chain.
+        ///
+        /// May be called by the guardian, only the admin can unpause.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = T::WeightInfo::pause_chain()]
+        pub fn pause_chain(origin, id: ChainId) -> DispatchResult {
+            Self::ensure_guardian(origin)?;
+            <PausedChains>::insert(id, true);
+
+            Self::deposit_event(RawEvent::ChainPaused(id));
+            Ok(())
+        }
+
+        /// Allows transfers from and to a chain again.
+        ///
+        /// # <weight>
+        /// - O(1) removal
+        /// # </weight>
+        #[weight = T::WeightInfo::unpause_chain()]
+        pub fn unpause_chain(origin, id: ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            <PausedChains>::remove(id);
+
+            Self::deposit_event(RawEvent::ChainUnpaused(id));
+            Ok(())
+        }
+
+        /// Pauses transfers of a resource.
+        ///
+        /// May be called by the guardian, only the admin can unpause.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = T::WeightInfo::pause_resource()]
+        pub fn pause_resource(origin, id: ResourceId) -> DispatchResult {
+            Self::ensure_guardian(origin)?;
+            <PausedResources>::insert(id, true);
+
+            Self::deposit_event(RawEvent::ResourcePaused(id));
+            Ok(())
+        }
+
+        /// Allows transfers of a resource again.
+        ///
+        /// # <weight>
+        /// - O(1) removal
+        /// # </weight>
+        #[weight = T::WeightInfo::unpause_resource()]
+        pub fn unpause_resource(origin, id: ResourceId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            <PausedResources>::remove(id);
+
+            Self::deposit_event(RawEvent::ResourceUnpaused(id));
+            Ok(())
+        }
+
+        /// Pays out the fees earned by the caller from the fees recipient account.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and transfer
+        /// # </weight>
+        #[weight = T::WeightInfo::claim_rewards()]
+        pub fn claim_rewards(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let amount = Self::relayer_rewards(&who);
+            ensure!(!amount.is_zero(), Error::<T>::NoRewards);
+            let fees_account = Self::fees_recipient_account()
+                .ok_or(Error::<T>::FeesRecipientAccountNotSet)?;
+
+            T::Currency::transfer(&fees_account, &who, amount, KeepAlive)?;
+            <RelayerRewards<T>>::remove(&who);
+
+            Self::deposit_event(RawEvent::RelayerRewarded(who, amount));
+            Ok(())
+        }
+
+        /// Unfreezes a nonce with conflicting proposals once `AdminDelay` has passed, only the proposal
+        /// whose call hashes to `call_hash` can be voted on afterwards.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = T::WeightInfo::unfreeze_proposal()]
+        pub fn unfreeze_proposal(origin, src_id: ChainId, nonce: DepositNonce, call_hash: T::Hash) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::announce_admin_action(AdminAction::UnfreezeProposal(src_id, nonce, call_hash))
+        }
+
+        /// Executes again a proposal whose call failed.
+        ///
+        /// Can be called by any relayer or the admin, unless the source chain or the resource is paused.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, regardless of whether execution is performed
+        /// # </weight>
+        #[weight = (
+            call.get_dispatch_info().weight.saturating_add(T::WeightInfo::retry_proposal(T::MaxRelayers::get())),
+            call.get_dispatch_info().class,
+            Pays::Yes,
+        )]
+        pub fn retry_proposal(origin, src_id: ChainId, nonce: DepositNonce, call: Box<T::Proposal>) -> DispatchResult {
+            let by_relayer = ensure_signed(origin.clone()).map(|who| Self::is_relayer(&who)).unwrap_or(false);
+            if !by_relayer {
+                Self::ensure_admin(origin)?;
+            }
+            let votes = <Votes<T>>::get(src_id, (nonce, call.clone()))
+                .ok_or(Error::<T>::ProposalDoesNotExist)?;
+            ensure!(votes.status == ProposalStatus::Failed, Error::<T>::ProposalNotFailed);
+            let (resource_id, _) = Self::failed_proposals(src_id, nonce).ok_or(Error::<T>::ProposalNotFailed)?;
+            Self::ensure_not_paused(src_id, resource_id)?;
+
+            Self::execute_proposal(src_id, nonce, resource_id, call, votes);
+            Ok(())
+        }
+
+        /// Attests the deposit made on the source chain for an inbound nonce, so that it can be refunded.
+        ///
+        /// The deposit is recorded once as many relayers as the threshold attested the same one.
+        ///
+        /// # <weight>
+        /// - O(R) attestations, R being at most `MaxRelayers`
+        /// # </weight>
+        #[weight = T::WeightInfo::attest_source_deposit(T::MaxRelayers::get())]
+        pub fn attest_source_deposit(origin, src_id: ChainId, nonce: DepositNonce, deposit: SourceDeposit) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
+            ensure!(Self::resources(deposit.resource_id).is_some(), Error::<T>::ResourceDoesNotExist);
+            ensure!(!<SourceDeposits>::contains_key(src_id, nonce), Error::<T>::SourceDepositAlreadyAttested);
+            Self::ensure_valid_recipient(src_id, &deposit.depositor)?;
+
+            let hash = T::Hashing::hash_of(&deposit);
+            let mut attested = Self::source_deposit_votes(src_id, nonce);
+            ensure!(!attested.iter().any(|(v, _)| v == &who), Error::<T>::RelayerAlreadyVoted);
+            attested.push((who, hash));
+
+            if attested.iter().filter(|(_, h)| h == &hash).count() >= Self::effective_threshold() as usize {
+                <SourceDepositVotes<T>>::remove(src_id, nonce);
+                <SourceDeposits>::insert(src_id, nonce, deposit);
+                Self::deposit_event(RawEvent::SourceDepositAttested(src_id, nonce));
+            } else {
+                <SourceDepositVotes<T>>::insert(src_id, nonce, attested);
+            }
+            Ok(())
+        }
+
+        /// Gives up on a failed proposal and sends its attested source-side deposit back to the
+        /// depositor, through a regular outbound transfer. The voters of the call get no reward.
+        ///
+        /// # <weight>
+        /// - O(R) slashes of the relayers which voted for another call, R being at most `MaxRelayers`
+        /// # </weight>
+        #[weight = T::WeightInfo::refund_proposal(T::MaxRelayers::get())]
+        pub fn refund_proposal(origin, src_id: ChainId, nonce: DepositNonce, call: Box<T::Proposal>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            let mut votes = <Votes<T>>::get(src_id, (nonce, call.clone()))
+                .ok_or(Error::<T>::ProposalDoesNotExist)?;
+            ensure!(votes.status == ProposalStatus::Failed, Error::<T>::ProposalNotFailed);
+
+            let deposit = Self::source_deposits(src_id, nonce).ok_or(Error::<T>::SourceDepositUnknown)?;
+            // only what an inbound transfer could have credited goes back
+            let amount = Self::to_local_amount(deposit.resource_id, deposit.amount)?;
+            Self::refund_source_deposit(src_id, nonce, deposit.resource_id, amount)?;
+
+            votes.status = ProposalStatus::Refunded;
+            <Votes<T>>::insert(src_id, (nonce, call.clone()), votes);
+            Self::finalize_nonce(src_id, nonce, *call, &[]);
+            Self::deposit_event(RawEvent::ProposalRefunded(src_id, nonce));
+            Ok(())
+        }
     }
 }
 
@@ -475,6 +1318,13 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Guardian, admin and root may pause
+    pub fn ensure_guardian(o: T::Origin) -> DispatchResult {
+        T::GuardianOrigin::try_origin(o)
+            .map(|_| ())
+            .or_else(Self::ensure_admin)
+    }
+
     /// Provides an AccountId for the pallet.
     /// This is used both as an origin check and deposit/withdrawal account.
     pub fn account_id() -> T::AccountId {
@@ -486,9 +1336,95 @@ impl<T: Trait> Module<T> {
         return Self::chains(id) != None;
     }
 
-    /// Get chain fees
-    pub fn get_chain_fees(id: ChainId) -> Option<Balance> {
-        return Self::chain_fees(id);
+    /// Checks `recipient` against the address format of `dest_id`
+    pub fn ensure_valid_recipient(dest_id: ChainId, recipient: &[u8]) -> DispatchResult {
+        let format = Self::address_formats(dest_id).ok_or(Error::<T>::AddressFormatNotSet)?;
+        ensure!(format.is_valid(recipient), Error::<T>::InvalidRecipient);
+        Ok(())
+    }
+
+    /// Converts a local amount of `resource_id` to the units of the other chains
+    pub fn to_foreign_amount(resource_id: ResourceId, amount: u128) -> Result<U256, DispatchError> {
+        Self::rescale(U256::from(amount), Self::resource_decimals(resource_id))
+    }
+
+    /// Converts an amount of `resource_id` received from another chain to local units
+    pub fn to_local_amount(resource_id: ResourceId, amount: U256) -> Result<u128, DispatchError> {
+        let decimals = Self::resource_decimals(resource_id).map(|(local, foreign)| (foreign, local));
+        let amount = Self::rescale(amount, decimals)?;
+        ensure!(amount <= U256::from(u128::max_value()), Error::<T>::AmountOverflow);
+        Ok(amount.as_u128())
+    }
+
+    /// Scales `amount` from `decimals.0` to `decimals.1`, refusing to drop any digit
+    fn rescale(amount: U256, decimals: Option<(u8, u8)>) -> Result<U256, DispatchError> {
+        let (from, to) = match decimals {
+            Some(decimals) => decimals,
+            None => return Ok(amount),
+        };
+        let exp = if from > to { from - to } else { to - from };
+        let factor = U256::from(10).checked_pow(U256::from(exp))
+            .ok_or(Error::<T>::AmountOverflow)?;
+        if to >= from {
+            Ok(amount.checked_mul(factor).ok_or(Error::<T>::AmountOverflow)?)
+        } else {
+            ensure!((amount % factor).is_zero(), Error::<T>::AmountNotRepresentable);
+            Ok(amount / factor)
+        }
+    }
+
+    /// Fee of a transfer of `amount` of `resource_id` towards `dest_id` and the currency it's paid in,
+    /// `None` if no fee schedule applies yet. The rate only applies to fees paid in the transferred asset,
+    /// an amount of some asset says nothing of its worth in AOW.
+    pub fn quote_fee(dest_id: ChainId, resource_id: ResourceId, amount: Balance) -> Option<(Balance, FeeCurrency)> {
+        let schedule = Self::fee_schedules(dest_id, resource_id)?;
+        let currency = Self::fee_currencies(dest_id, resource_id);
+        let rated_amount = match currency {
+            FeeCurrency::Asset => amount,
+            FeeCurrency::Native => Zero::zero(),
+        };
+        Some((schedule.fee_for(rated_amount), currency))
+    }
+
+    /// Applies the median of the submissions of the current proxy accounts, if there are enough of them
+    fn update_fee_schedule(id: ChainId, resource_id: ResourceId) {
+        let schedules: Vec<FeeSchedule> = Self::fee_submissions(id, resource_id)
+            .into_iter()
+            .filter(|(who, _)| <ProxyAccounts<T>>::contains_key(who))
+            .map(|(_, schedule)| schedule)
+            .collect();
+        if (schedules.len() as u32) < T::MinFeeSubmissions::get() {
+            return;
+        }
+        if let Some(schedule) = FeeSchedule::median(&schedules) {
+            if Self::fee_schedules(id, resource_id) != Some(schedule) {
+                <FeeSchedules>::insert(id, resource_id, schedule);
+                Self::deposit_event(RawEvent::FeeScheduleSet(id, resource_id, schedule));
+            }
+        }
+    }
+
+    /// Whitelisted chains and their current deposit nonce
+    pub fn whitelisted_chains() -> Vec<(ChainId, DepositNonce)> {
+        <ChainNonces>::iter().collect()
+    }
+
+    /// Proposals from `src_id` which are still open for voting.
+    /// This iterates over all the proposals of the chain and is meant for off-chain queries.
+    pub fn pending_proposals(src_id: ChainId) -> Vec<(DepositNonce, T::Proposal, ProposalVotes<T::AccountId, T::BlockNumber>)> {
+        <Votes<T>>::iter_prefix(src_id)
+            .filter(|(_, votes)| votes.status == ProposalStatus::Active)
+            .map(|((nonce, prop), votes)| (nonce, prop, votes))
+            .collect()
+    }
+
+    /// All the proposals submitted for an inbound nonce.
+    /// This iterates over all the proposals of the chain and is meant for off-chain queries.
+    pub fn nonce_proposals(src_id: ChainId, nonce: DepositNonce) -> Vec<(T::Proposal, ProposalVotes<T::AccountId, T::BlockNumber>)> {
+        <Votes<T>>::iter_prefix(src_id)
+            .filter(|((n, _), _)| *n == nonce)
+            .map(|((_, prop), votes)| (prop, votes))
+            .collect()
     }
 
     /// Provides an AccountId for the fees.
@@ -501,6 +1437,13 @@ impl<T: Trait> Module<T> {
         return Self::is_paused();
     }
 
+    /// Ensures neither the chain nor the resource of a transfer is paused
+    pub fn ensure_not_paused(chain_id: ChainId, resource_id: ResourceId) -> DispatchResult {
+        ensure!(!Self::paused_chains(chain_id), Error::<T>::ChainPaused);
+        ensure!(!Self::paused_resources(resource_id), Error::<T>::ResourcePaused);
+        Ok(())
+    }
+
     /// Increments the deposit nonce for the specified chain ID
     fn bump_nonce(id: ChainId) -> DepositNonce {
         let nonce = Self::chains(id).unwrap_or_default() + 1;
@@ -534,7 +1477,9 @@ impl<T: Trait> Module<T> {
             Self::chain_whitelisted(dest_id),
             Error::<T>::ChainNotWhitelisted
         );
+        Self::ensure_not_paused(dest_id, resource_id)?;
         let nonce = Self::bump_nonce(dest_id);
+        Self::record_deposit(dest_id, nonce, source.clone(), resource_id, DepositData::Fungible(amount, to.clone()));
         Self::deposit_event(RawEvent::FungibleTransfer(
             source,
             dest_id,
@@ -546,19 +1491,267 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Sends `amount` of `resource_id`, in local units, back to the attested depositor of an inbound
+    /// nonce on its source chain. The amount is scaled like the one of any outbound transfer.
+    pub fn refund_source_deposit(src_id: ChainId, nonce: DepositNonce, resource_id: ResourceId, amount: u128) -> DispatchResult {
+        let deposit = Self::source_deposits(src_id, nonce).ok_or(Error::<T>::SourceDepositUnknown)?;
+        ensure!(deposit.resource_id == resource_id, Error::<T>::SourceDepositMismatch);
+        Self::ensure_valid_recipient(src_id, &deposit.depositor)?;
+        let foreign_amount = Self::to_foreign_amount(resource_id, amount)?;
+
+        Self::transfer_fungible(Self::account_id(), src_id, resource_id, deposit.depositor, foreign_amount)?;
+        <SourceDeposits>::remove(src_id, nonce);
+        Ok(())
+    }
+
+    /// Initiates a transfer of a non-fungible asset out of the chain. This should be called by another pallet.
+    pub fn transfer_nonfungible(
+        source: T::AccountId,
+        dest_id: ChainId,
+        resource_id: ResourceId,
+        token_id: U256,
+        to: Vec<u8>,
+        metadata: Vec<u8>,
+    ) -> DispatchResult {
+        ensure!(
+            Self::chain_whitelisted(dest_id),
+            Error::<T>::ChainNotWhitelisted
+        );
+        Self::ensure_not_paused(dest_id, resource_id)?;
+        let nonce = Self::bump_nonce(dest_id);
+        Self::record_deposit(
+            dest_id,
+            nonce,
+            source.clone(),
+            resource_id,
+            DepositData::NonFungible(token_id, to.clone(), metadata.clone()),
+        );
+        Self::deposit_event(RawEvent::NonFungibleTransfer(
+            source,
+            dest_id,
+            nonce,
+            resource_id,
+            token_id,
+            to,
+            metadata,
+        ));
+        Ok(())
+    }
+
+    /// Initiates a transfer of generic data out of the chain. This should be called by another pallet.
+    pub fn transfer_generic(
+        source: T::AccountId,
+        dest_id: ChainId,
+        resource_id: ResourceId,
+        data: Vec<u8>,
+    ) -> DispatchResult {
+        ensure!(
+            Self::chain_whitelisted(dest_id),
+            Error::<T>::ChainNotWhitelisted
+        );
+        Self::ensure_not_paused(dest_id, resource_id)?;
+        let nonce = Self::bump_nonce(dest_id);
+        Self::record_deposit(dest_id, nonce, source.clone(), resource_id, DepositData::Generic(data.clone()));
+        Self::deposit_event(RawEvent::GenericTransfer(
+            source,
+            dest_id,
+            nonce,
+            resource_id,
+            data,
+        ));
+        Ok(())
+    }
+
+    /// Stores an outbound deposit and drops the one falling out of the retention window
+    fn record_deposit(dest_id: ChainId, nonce: DepositNonce, sender: T::AccountId, resource_id: ResourceId, data: DepositData) {
+        let record = DepositRecord {
+            sender,
+            resource_id,
+            data,
+            block: system::Module::<T>::block_number(),
+        };
+        <DepositRecords<T>>::insert(dest_id, nonce, record);
+
+        let retention = T::DepositRecordsRetention::get();
+        if nonce > retention {
+            <DepositRecords<T>>::remove(dest_id, nonce - retention);
+        }
+    }
+
+    /// Outbound deposits towards `dest_id` from nonce `from` on, at most `limit` of them
+    pub fn deposit_records_from(dest_id: ChainId, from: DepositNonce, limit: u32) -> Vec<(DepositNonce, DepositRecord<T::AccountId, T::BlockNumber>)> {
+        let last = Self::chains(dest_id).unwrap_or_default();
+        let limit = (limit as DepositNonce).min(T::DepositRecordsRetention::get());
+        (from.max(1)..=last)
+            .take(limit as usize)
+            .filter_map(|nonce| Self::deposit_records(dest_id, nonce).map(|r| (nonce, r)))
+            .collect()
+    }
+
     /// Checks if who is a relayer
     pub fn is_relayer(who: &T::AccountId) -> bool {
         Self::relayers(who)
     }
 
-    /// Commits a vote for a proposal. If the proposal doesn't exist it will be created.
-    fn commit_vote(who: T::AccountId, nonce: DepositNonce, src_id: ChainId, prop: Box<T::Proposal>) -> DispatchResult {
+    /// Number of votes required for a proposal to pass with the current relayer set
+    pub fn effective_threshold() -> u32 {
+        Self::threshold_for(Self::relayer_count())
+    }
+
+    /// Number of votes required for a proposal to pass with `count` relayers
+    fn threshold_for(count: u32) -> u32 {
+        match Self::relayer_threshold_ratio() {
+            Some(ratio) => ratio.mul_ceil(count).max(1),
+            None => Self::relayer_threshold(),
+        }
+    }
+
+    /// Emits `RelayerThresholdChanged` if the effective threshold moved away from `old`
+    fn note_threshold_change(old: u32) {
+        let new = Self::effective_threshold();
+        if new != old {
+            Self::deposit_event(RawEvent::RelayerThresholdChanged(new));
+        }
+    }
+
+    /// Queues `action` to apply once `AdminDelay` has passed, or applies it right away without a delay.
+    /// Actions which couldn't apply at the time of the announcement are refused.
+    fn announce_admin_action(action: AdminAction<T::AccountId, T::Hash>) -> DispatchResult {
+        let delay = T::AdminDelay::get();
+        if delay.is_zero() {
+            return Self::apply_admin_action(action);
+        }
+        with_transaction(|| TransactionOutcome::Rollback(Self::apply_admin_action(action.clone())))?;
+
+        let id = Self::next_admin_action_id();
+        let at = system::Module::<T>::block_number() + delay;
+        <NextAdminActionId>::put(id.wrapping_add(1));
+        <AdminActions<T>>::insert(id, (at, action.clone()));
+        <AdminActionQueue<T>>::append(at, id);
+
+        Self::deposit_event(RawEvent::AdminActionAnnounced(id, action, at));
+        Ok(())
+    }
+
+    /// Applies the admin actions whose delay ends at `now`
+    fn apply_due_admin_actions(now: T::BlockNumber) -> Weight {
+        let due = <AdminActionQueue<T>>::take(now);
+        for id in due.iter() {
+            let action = match <AdminActions<T>>::take(id) {
+                Some((_, action)) => action,
+                None => continue,
+            };
+            let result = with_transaction(|| match Self::apply_admin_action(action) {
+                Ok(()) => TransactionOutcome::Commit(Ok(())),
+                Err(e) => TransactionOutcome::Rollback(Err(e)),
+            });
+            match result {
+                Ok(()) => Self::deposit_event(RawEvent::AdminActionApplied(*id)),
+                Err(e) => Self::deposit_event(RawEvent::AdminActionFailed(*id, e)),
+            }
+        }
+
+        let applied = due.len() as Weight;
+        T::DbWeight::get().reads_writes(1, 1)
+            .saturating_add(applied.saturating_mul(T::WeightInfo::add_relayer()))
+    }
+
+    fn apply_admin_action(action: AdminAction<T::AccountId, T::Hash>) -> DispatchResult {
+        match action {
+            AdminAction::SetThreshold(threshold) => {
+                ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+                ensure!(threshold <= Self::relayer_count(), Error::<T>::ThresholdUnreachable);
+                <RelayerThreshold>::put(threshold);
+                <RelayerThresholdRatio>::kill();
+                Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+            },
+            AdminAction::SetThresholdRatio(ratio) => {
+                ensure!(ratio != Perbill::from_parts(0), Error::<T>::InvalidThreshold);
+                <RelayerThresholdRatio>::put(ratio);
+                Self::deposit_event(RawEvent::RelayerThresholdChanged(Self::effective_threshold()));
+            },
+            AdminAction::AddRelayer(relayer) => {
+                ensure!(!Self::is_relayer(&relayer), Error::<T>::RelayerAlreadyExists);
+                ensure!(Self::relayer_count() < T::MaxRelayers::get(), Error::<T>::TooManyRelayers);
+
+                let bond = T::RelayerBond::get();
+                T::Currency::reserve(&relayer, bond)?;
+                <RelayerBonds<T>>::insert(&relayer, bond);
+                <Relayers<T>>::insert(&relayer, true);
+                let old_threshold = Self::effective_threshold();
+                <RelayerCount>::mutate(|i| *i += 1);
+
+                Self::deposit_event(RawEvent::RelayerAdded(relayer));
+                Self::note_threshold_change(old_threshold);
+            },
+            AdminAction::RemoveRelayer(relayer) => {
+                ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
+                let new_count = Self::relayer_count() - 1;
+                ensure!(new_count >= Self::threshold_for(new_count), Error::<T>::ThresholdUnreachable);
+
+                T::Currency::unreserve(&relayer, <RelayerBonds<T>>::take(&relayer));
+                <Relayers<T>>::remove(&relayer);
+                <RelayerKeys<T>>::remove(&relayer);
+                let old_threshold = Self::effective_threshold();
+                <RelayerCount>::put(new_count);
+
+                Self::deposit_event(RawEvent::RelayerRemoved(relayer));
+                Self::note_threshold_change(old_threshold);
+            },
+            AdminAction::AddResource(id, method) => <Resources>::insert(id, method),
+            AdminAction::SetFeesRecipientAccount(account) => <FeesRecipientAccount<T>>::put(account),
+            AdminAction::MapResourceAndRsymbol(resource_id, sym) => {
+                <ResourceRsymbol>::insert(&resource_id, &sym);
+                <RsymbolResource>::insert(&sym, &resource_id);
+            },
+            AdminAction::UnmapResourceAndRsymbol(resource_id, sym) => {
+                <ResourceRsymbol>::remove(&resource_id);
+                <RsymbolResource>::remove(&sym);
+            },
+            AdminAction::SetResourceDecimals(resource_id, local, foreign) => {
+                <ResourceDecimals>::insert(&resource_id, (local, foreign));
+                Self::deposit_event(RawEvent::ResourceDecimalsSet(resource_id, local, foreign));
+            },
+            AdminAction::UnfreezeProposal(src_id, nonce, call_hash) => {
+                ensure!(Self::frozen_nonces(src_id, nonce), Error::<T>::ProposalNotFrozen);
+                <FrozenNonces>::remove(src_id, nonce);
+                <NonceProposal<T>>::insert(src_id, nonce, call_hash);
+                Self::deposit_event(RawEvent::ProposalUnfrozen(src_id, nonce, call_hash));
+            },
+        }
+        Ok(())
+    }
+
+    /// Message relayers sign to vote for a proposal through `submit_signed_proposal`, bound to this
+    /// chain so it can't be replayed on another chain of the bridge
+    pub fn proposal_message(src_id: ChainId, nonce: DepositNonce, resource_id: ResourceId, prop: &T::Proposal) -> Vec<u8> {
+        let mut msg = PROPOSAL_MESSAGE_DOMAIN.to_vec();
+        (T::ChainIdentity::get(), src_id, nonce, resource_id, T::Hashing::hash_of(prop)).encode_to(&mut msg);
+        msg
+    }
+
+    /// Commits a vote for or against a proposal. If the proposal doesn't exist it will be created.
+    fn commit_vote(
+        who: T::AccountId,
+        nonce: DepositNonce,
+        src_id: ChainId,
+        resource_id: ResourceId,
+        prop: Box<T::Proposal>,
+        in_favour: bool,
+    ) -> DispatchResult {
+        Self::check_nonce_proposal(&who, src_id, nonce, &prop, in_favour)?;
+
         let now = system::Module::<T>::block_number();
-        let mut votes = <Votes<T>>::get(src_id, (nonce, prop.clone())).unwrap_or_else(|| {
-            let mut v = ProposalVotes::default();
-            v.expiry = now + T::ProposalLifetime::get();
-            v
-        });
+        let mut votes = match <Votes<T>>::get(src_id, (nonce, prop.clone())) {
+            Some(v) => v,
+            None => {
+                let mut v = ProposalVotes::default();
+                v.expiry = now + T::ProposalLifetime::get();
+                v.resource_id = resource_id;
+                <ExpiryQueue<T>>::append(v.expiry, (src_id, (nonce, *prop.clone())));
+                v
+            }
+        };
 
         // Ensure the proposal isn't complete and relayer hasn't already voted
         ensure!(!votes.is_completed(), Error::<T>::ProposalAlreadyCompleted);
@@ -566,33 +1759,78 @@ impl<T: Trait> Module<T> {
         if votes.is_expired(now) {
             votes.status = ProposalStatus::Expired;
             <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+            Self::schedule_prune(now, src_id, nonce, *prop.clone());
+            Self::slash_expired(src_id, &votes);
         }
         ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
 
-        votes.voted.push(who.clone());
-        votes.derivate(Self::relayer_threshold(), now);
+        if in_favour {
+            votes.voted.push(who.clone());
+            Self::deposit_event(RawEvent::VoteFor(src_id, nonce, who.clone()));
+        } else {
+            votes.votes_against.push(who.clone());
+            Self::deposit_event(RawEvent::VoteAgainst(src_id, nonce, who.clone()));
+        }
+        votes.derivate(Self::effective_threshold(), Self::relayer_count(), now);
         <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes);
 
-        Self::deposit_event(RawEvent::VoteFor(src_id, nonce, who.clone()));
+        Ok(())
+    }
+
+    /// Ensures `prop` is the only proposal for an inbound nonce which hasn't been executed yet.
+    /// A different call for the same nonce freezes it until an admin picks the right one.
+    fn check_nonce_proposal(
+        who: &T::AccountId,
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop: &T::Proposal,
+        in_favour: bool,
+    ) -> DispatchResult {
+        ensure!(!Self::nonce_executed(src_id, nonce), Error::<T>::NonceAlreadyExecuted);
+
+        ensure!(!Self::frozen_nonces(src_id, nonce), Error::<T>::ProposalFrozen);
+        // only votes in favour claim the nonce
+        if !in_favour {
+            return Ok(());
+        }
+
+        let hash = T::Hashing::hash_of(prop);
+        // remember what each relayer stood for, to slash the losing side once the nonce executes
+        <NonceVoters<T>>::mutate(src_id, nonce, |voters| {
+            if !voters.iter().any(|(v, h)| v == who && h == &hash) {
+                voters.push((who.clone(), hash));
+            }
+        });
+
+        match Self::nonce_proposal(src_id, nonce) {
+            None => <NonceProposal<T>>::insert(src_id, nonce, hash),
+            Some(existing) if existing != hash => {
+                <FrozenNonces>::insert(src_id, nonce, true);
+                Self::deposit_event(RawEvent::ProposalConflict(src_id, nonce, existing, hash));
+                return Err(Error::<T>::ProposalFrozen.into());
+            },
+            _ => (),
+        }
         Ok(())
     }
 
     /// Attempts to finalize or cancel the proposal if the vote count allows.
-    fn try_resolve_proposal(nonce: DepositNonce, src_id: ChainId, prop: Box<T::Proposal>) -> DispatchResult {
+    fn try_resolve_proposal(nonce: DepositNonce, src_id: ChainId, resource_id: ResourceId, prop: Box<T::Proposal>) -> DispatchResult {
         let op_votes = <Votes<T>>::get(src_id, (nonce, prop.clone()));
         ensure!(op_votes.is_some(), Error::<T>::ProposalDoesNotExist);
 
-        let mut votes = op_votes.unwrap();
+        let votes = op_votes.unwrap();
         match votes.status {
             ProposalStatus::Passed => {
                 Self::deposit_event(RawEvent::ProposalPassed(src_id, nonce));
-                let call = prop.clone();
-                call.dispatch(system::RawOrigin::Signed(Self::account_id()).into())
-                    .map(|_| ())
-                    .map_err(|e| e.error)?;
-                votes.to_be_executed();
-                <Votes<T>>::insert(src_id, (nonce, prop), votes.clone());
-                Self::deposit_event(RawEvent::ProposalExecuted(src_id, nonce));
+                Self::execute_proposal(src_id, nonce, resource_id, prop, votes);
+                Ok(())
+            },
+            ProposalStatus::Rejected => {
+                Self::slash_relayers(&votes.voted);
+                Self::release_nonce_claim(src_id, nonce, T::Hashing::hash_of(&*prop));
+                Self::schedule_prune(system::Module::<T>::block_number(), src_id, nonce, *prop);
+                Self::deposit_event(RawEvent::ProposalCancelled(src_id, nonce));
                 Ok(())
             },
             ProposalStatus::Expired => {
@@ -602,6 +1840,200 @@ impl<T: Trait> Module<T> {
             _ => Ok(()),
         }
     }
+
+    /// Lets another call be proposed for a nonce which `hash` claimed, unless the nonce is frozen.
+    /// The relayers which voted for `hash` are dropped, they were slashed already.
+    fn release_nonce_claim(src_id: ChainId, nonce: DepositNonce, hash: T::Hash) {
+        if Self::frozen_nonces(src_id, nonce) {
+            return;
+        }
+        if Self::nonce_proposal(src_id, nonce) == Some(hash) {
+            <NonceProposal<T>>::remove(src_id, nonce);
+        }
+        <NonceVoters<T>>::mutate(src_id, nonce, |voters| voters.retain(|(_, h)| h != &hash));
+    }
+
+    /// Dispatches the call of a passed proposal. A failing call leaves no change behind
+    /// and marks the proposal as failed, instead of reverting the vote which passed it.
+    fn execute_proposal(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        resource_id: ResourceId,
+        prop: Box<T::Proposal>,
+        mut votes: ProposalVotes<T::AccountId, T::BlockNumber>,
+    ) {
+        let call = prop.clone();
+        // proposals voted before the filter got stricter fail instead of dispatching
+        let result = if T::ProposalFilter::filter(&call) {
+            <ExecutingNonce>::put((src_id, nonce));
+            let result = with_transaction(|| {
+                match call.dispatch(system::RawOrigin::Signed(Self::account_id()).into()) {
+                    Ok(_) => TransactionOutcome::Commit(Ok(())),
+                    Err(e) => TransactionOutcome::Rollback(Err(e.error)),
+                }
+            });
+            <ExecutingNonce>::kill();
+            result
+        } else {
+            Err(Error::<T>::ProposalNotAllowed.into())
+        };
+
+        match result {
+            Ok(()) => {
+                votes.to_be_executed();
+                <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+                Self::finalize_nonce(src_id, nonce, *prop, &votes.voted);
+                Self::deposit_event(RawEvent::ProposalExecuted(src_id, nonce));
+            },
+            Err(e) => {
+                votes.status = ProposalStatus::Failed;
+                <Votes<T>>::insert(src_id, (nonce, prop), votes);
+                <FailedProposals>::insert(src_id, nonce, (resource_id, e));
+                Self::deposit_event(RawEvent::ProposalFailed(src_id, nonce, e));
+            },
+        }
+    }
+
+    /// Closes an inbound nonce once its proposal got executed or refunded
+    fn finalize_nonce(src_id: ChainId, nonce: DepositNonce, prop: T::Proposal, voted: &[T::AccountId]) {
+        let hash = T::Hashing::hash_of(&prop);
+        Self::mark_executed(src_id, nonce, hash);
+        <NonceProposal<T>>::remove(src_id, nonce);
+        <FailedProposals>::remove(src_id, nonce);
+        Self::settle_relayers(src_id, nonce, hash, voted);
+        Self::schedule_prune(system::Module::<T>::block_number(), src_id, nonce, prop);
+    }
+
+    /// Whether an inbound nonce got executed, or fell out of the `ExecutedNoncesWindow`
+    pub fn nonce_executed(src_id: ChainId, nonce: DepositNonce) -> bool {
+        Self::executed_nonces(src_id, nonce).is_some() ||
+            nonce.saturating_add(T::ExecutedNoncesWindow::get()) <= Self::executed_nonces_mark(src_id)
+    }
+
+    /// Records an executed inbound nonce and forgets the ones which fell out of the window.
+    fn mark_executed(src_id: ChainId, nonce: DepositNonce, hash: T::Hash) {
+        <ExecutedNonces<T>>::insert(src_id, nonce, hash);
+
+        let mark = Self::executed_nonces_mark(src_id);
+        if nonce <= mark {
+            return;
+        }
+        <ExecutedNoncesMark>::insert(src_id, nonce);
+
+        // nonces up to the previous cutoff are gone already and none above the previous mark was executed
+        let window = T::ExecutedNoncesWindow::get();
+        let cutoff = nonce.saturating_sub(window).min(mark);
+        for old in mark.saturating_sub(window)..=cutoff {
+            <ExecutedNonces<T>>::remove(src_id, old);
+        }
+    }
+
+    /// Queues a completed proposal for removal from `Votes` once the retention window has passed.
+    fn schedule_prune(now: T::BlockNumber, src_id: ChainId, nonce: DepositNonce, prop: T::Proposal) {
+        <PruneQueue<T>>::append(now + T::VotesRetention::get(), (src_id, (nonce, prop)));
+    }
+
+    /// Expires and prunes the proposals queued for `now`, at most `MaxSweepPerBlock` of them.
+    /// Whatever exceeds the limit is carried over to the next block.
+    fn sweep_proposals(now: T::BlockNumber) -> Weight {
+        let mut budget = T::MaxSweepPerBlock::get() as usize;
+        let next = now + One::one();
+
+        let mut expiring = <ExpiryQueue<T>>::take(now);
+        if expiring.len() > budget {
+            let carried = expiring.split_off(budget);
+            <ExpiryQueue<T>>::mutate(next, |q| q.extend(carried));
+        }
+        budget -= expiring.len();
+        let expired_count = expiring.len() as Weight;
+
+        for (src_id, (nonce, prop)) in expiring {
+            let key = (nonce, prop);
+            if let Some(mut votes) = <Votes<T>>::get(src_id, &key) {
+                if votes.is_completed() {
+                    continue;
+                }
+                votes.status = ProposalStatus::Expired;
+                Self::slash_expired(src_id, &votes);
+                <Votes<T>>::insert(src_id, &key, votes);
+                Self::schedule_prune(now, src_id, key.0, key.1);
+                Self::deposit_event(RawEvent::ProposalCancelled(src_id, nonce));
+            }
+        }
+
+        let mut pruning = <PruneQueue<T>>::take(now);
+        if pruning.len() > budget {
+            let carried = pruning.split_off(budget);
+            <PruneQueue<T>>::mutate(next, |q| q.extend(carried));
+        }
+        let pruned_count = pruning.len() as Weight;
+
+        for (src_id, key) in pruning {
+            // let the nonce be proposed again unless it's frozen or got executed
+            if Self::nonce_proposal(src_id, key.0) == Some(T::Hashing::hash_of(&key.1)) &&
+                !Self::frozen_nonces(src_id, key.0) {
+                <NonceProposal<T>>::remove(src_id, key.0);
+                <NonceVoters<T>>::remove(src_id, key.0);
+            }
+            <Votes<T>>::remove(src_id, key);
+        }
+
+        let max_relayers = Self::relayer_count() as Weight;
+        T::DbWeight::get().reads_writes(
+            5 + expired_count * (4 + max_relayers * 2) + pruned_count * 3,
+            4 + expired_count * (2 + max_relayers * 2) + pruned_count * 3,
+        )
+    }
+
+    /// Rewards the relayers which voted for the executed call of a nonce and
+    /// slashes the ones which voted for a conflicting one.
+    fn settle_relayers(src_id: ChainId, nonce: DepositNonce, executed: T::Hash, voted: &[T::AccountId]) {
+        let losers: Vec<T::AccountId> = <NonceVoters<T>>::take(src_id, nonce)
+            .into_iter()
+            .filter(|(_, hash)| hash != &executed)
+            .map(|(who, _)| who)
+            .collect();
+        Self::slash_relayers(&losers);
+
+        if voted.is_empty() {
+            return;
+        }
+        let share = T::RelayerReward::get() / (voted.len() as u32).into();
+        if share.is_zero() {
+            return;
+        }
+        for who in voted {
+            <RelayerRewards<T>>::mutate(who, |r| *r = r.saturating_add(share));
+        }
+    }
+
+    /// Slashes the relayers which voted for an expired proposal, unless its source chain or resource
+    /// is paused: the pause refused the votes which would have carried it.
+    fn slash_expired(src_id: ChainId, votes: &ProposalVotes<T::AccountId, T::BlockNumber>) {
+        if Self::paused_chains(src_id) || Self::paused_resources(votes.resource_id) {
+            return;
+        }
+        Self::slash_relayers(&votes.voted);
+    }
+
+    /// Slashes `RelayerSlash` from the bond of each of `relayers`, at most what is left of it.
+    fn slash_relayers(relayers: &[T::AccountId]) {
+        let amount = T::RelayerSlash::get();
+        if amount.is_zero() {
+            return;
+        }
+        for who in relayers {
+            let bond = Self::relayer_bonds(who);
+            if bond.is_zero() {
+                continue;
+            }
+            let (imbalance, remaining) = T::Currency::slash_reserved(who, amount.min(bond));
+            let slashed = amount.min(bond).saturating_sub(remaining);
+            <RelayerBonds<T>>::insert(who, bond.saturating_sub(slashed));
+            T::Slash::on_unbalanced(imbalance);
+            Self::deposit_event(RawEvent::RelayerSlashed(who.clone(), slashed));
+        }
+    }
 }
 
 /// Simple ensure origin for the bridge account
@@ -11,10 +11,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
+use sp_std::cell::RefCell;
 use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
 use sp_core::H256;
-use frame_support::{assert_ok, impl_outer_origin, impl_outer_event, impl_outer_dispatch, parameter_types, weights::Weight};
-use frame_system::{EnsureRoot};
+use frame_support::{assert_ok, impl_outer_origin, impl_outer_event, impl_outer_dispatch, parameter_types, ord_parameter_types, traits::{Filter, Get}, weights::Weight};
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use node_primitives::{ChainId, BlockNumber};
 use crate as bridge_common;
 use crate::{Module, Trait, ResourceId};
@@ -25,6 +26,7 @@ impl_outer_origin!{
 impl_outer_event!{
 	pub enum TestEvent for Test {
 		frame_system<T>,
+		pallet_balances<T>,
 		bridge_common<T>,
 	}
 }
@@ -32,6 +34,7 @@ impl_outer_event!{
 impl_outer_dispatch! {
 	pub enum Call for Test where origin: Origin {
 		frame_system::System,
+		pallet_balances::Balances,
 		self::BridgeCommon,
 	}
 }
@@ -71,38 +74,118 @@ impl frame_system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = TestEvent;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+ord_parameter_types! {
+	pub const Guardian: u64 = 0x9;
+}
+
 parameter_types! {
 	pub const ChainIdentity: ChainId = 1;
 	pub const ProposalLifetime: BlockNumber = 50;
+	pub const VotesRetention: BlockNumber = 10;
+	pub const MaxSweepPerBlock: u32 = 2;
+	pub const DepositRecordsRetention: u64 = 3;
+	pub const ExecutedNoncesWindow: u64 = 3;
+	pub const MinFeeSubmissions: u32 = 2;
+	pub const MaxProxyAccounts: u32 = 4;
+	pub const MaxFeeSchedules: u32 = 4;
+	pub const MaxRelayers: u32 = 4;
+	pub const RelayerBond: u64 = 10;
+	pub const RelayerReward: u64 = 6;
+	pub const RelayerSlash: u64 = 4;
+}
+
+thread_local! {
+	static ADMIN_DELAY: RefCell<u64> = RefCell::new(0);
+}
+
+pub struct AdminDelay;
+impl AdminDelay {
+	pub fn set(delay: u64) {
+		ADMIN_DELAY.with(|v| *v.borrow_mut() = delay);
+	}
+}
+impl Get<u64> for AdminDelay {
+	fn get() -> u64 {
+		ADMIN_DELAY.with(|v| *v.borrow())
+	}
+}
+
+/// Relayer proposals can't administer the bridge
+pub struct ProposalFilter;
+impl Filter<Call> for ProposalFilter {
+	fn filter(call: &Call) -> bool {
+		!matches!(call, Call::BridgeCommon(_))
+	}
 }
 
 impl Trait for Test {
 	type Event = TestEvent;
 	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type GuardianOrigin = EnsureSignedBy<Guardian, u64>;
 	type ChainIdentity = ChainIdentity;
 	type Proposal = Call;
+	type ProposalFilter = ProposalFilter;
 	type ProposalLifetime = ProposalLifetime;
+	type VotesRetention = VotesRetention;
+	type MaxSweepPerBlock = MaxSweepPerBlock;
+	type DepositRecordsRetention = DepositRecordsRetention;
+	type ExecutedNoncesWindow = ExecutedNoncesWindow;
+	type MinFeeSubmissions = MinFeeSubmissions;
+	type MaxProxyAccounts = MaxProxyAccounts;
+	type MaxFeeSchedules = MaxFeeSchedules;
+	type MaxRelayers = MaxRelayers;
+	type AdminDelay = AdminDelay;
+	type Currency = Balances;
+	type RelayerBond = RelayerBond;
+	type RelayerReward = RelayerReward;
+	type RelayerSlash = RelayerSlash;
+	type Slash = ();
+	type WeightInfo = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(FEES_ACCOUNT, ENDOWED_BALANCE),
+			(RELAYER_A, ENDOWED_BALANCE),
+			(RELAYER_B, ENDOWED_BALANCE),
+			(RELAYER_C, ENDOWED_BALANCE),
+		],
+	}.assimilate_storage(&mut t).unwrap();
 	t.into()
 }
 
 pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
 pub type BridgeCommon = Module<Test>;
 
 // Relayers
 pub const RELAYER_A: u64 = 0x2;
 pub const RELAYER_B: u64 = 0x3;
 pub const RELAYER_C: u64 = 0x4;
-// // pub const ENDOWED_BALANCE: u64 = 100_000_000;
+pub const FEES_ACCOUNT: u64 = 0x1;
+pub const ENDOWED_BALANCE: u64 = 100;
 pub const TEST_THRESHOLD: u32 = 2;
 
 pub fn new_test_ext_initialized(
@@ -112,13 +195,13 @@ pub fn new_test_ext_initialized(
 ) -> sp_io::TestExternalities {
     let mut t = new_test_ext();
     t.execute_with(|| {
-        // Set and check threshold
-        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
-        assert_eq!(BridgeCommon::relayer_threshold(), TEST_THRESHOLD);
         // Add relayers
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_C));
+        // Set and check threshold
+        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_eq!(BridgeCommon::relayer_threshold(), TEST_THRESHOLD);
         // Whitelist chain
         assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), src_id));
         // Set and check resource ID mapped to some junk data
@@ -0,0 +1,50 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Keys relayers sign proposals with, off chain.
+
+use codec::{Decode, Encode};
+use sp_core::{ecdsa, sr25519};
+use sp_runtime::RuntimeDebug;
+
+/// Public key registered by a relayer
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum RelayerKey {
+    Sr25519(sr25519::Public),
+    /// Compressed secp256k1 public key
+    Ecdsa(ecdsa::Public),
+}
+
+/// Signature of a relayer over a proposal
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum RelayerSignature {
+    Sr25519(sr25519::Signature),
+    /// Recoverable secp256k1 signature of the blake2_256 hash of the message
+    Ecdsa(ecdsa::Signature),
+}
+
+impl RelayerKey {
+    /// Whether `signature` is a signature of `msg` by this key
+    pub fn verify(&self, msg: &[u8], signature: &RelayerSignature) -> bool {
+        match (self, signature) {
+            (RelayerKey::Sr25519(key), RelayerSignature::Sr25519(sig)) =>
+                sp_io::crypto::sr25519_verify(sig, msg, key),
+            (RelayerKey::Ecdsa(key), RelayerSignature::Ecdsa(sig)) => {
+                let hash = sp_io::hashing::blake2_256(msg);
+                match sp_io::crypto::secp256k1_ecdsa_recover_compressed(sig.as_ref(), &hash) {
+                    Ok(recovered) => AsRef::<[u8]>::as_ref(key) == &recovered[..],
+                    Err(_) => false,
+                }
+            },
+            _ => false,
+        }
+    }
+}
@@ -13,10 +13,11 @@
 
 use super::*;
 use super::mock::{*, Call};
-use frame_support::{assert_ok, assert_noop, assert_err};
-use sp_runtime::traits::BadOrigin;
+use frame_support::{assert_ok, assert_noop, assert_err, traits::OnInitialize};
+use sp_runtime::{Perbill, traits::BadOrigin};
 use node_primitives::{RSymbol};
 use sp_io::hashing::blake2_128;
+use sp_core::Pair;
 
 #[test]
 fn new_resource_id() {
@@ -97,6 +98,14 @@ fn set_get_threshold() {
     new_test_ext().execute_with(|| {
         assert_eq!(<RelayerThreshold>::get(), 1);
 
+        // the threshold can't exceed the number of relayers
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
+        assert_noop!(
+            BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD),
+            Error::<Test>::ThresholdUnreachable
+        );
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+
         assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
 		assert_eq!(<RelayerThreshold>::get(), TEST_THRESHOLD);
 
@@ -112,6 +121,69 @@ fn set_get_threshold() {
     })
 }
 
+#[test]
+fn threshold_ratio_tracks_relayer_count() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            BridgeCommon::set_threshold_ratio(Origin::signed(42), Perbill::from_percent(50)),
+            BadOrigin
+        );
+        assert_noop!(
+            BridgeCommon::set_threshold_ratio(Origin::root(), Perbill::from_percent(0)),
+            Error::<Test>::InvalidThreshold
+        );
+
+        assert_ok!(BridgeCommon::set_threshold_ratio(Origin::root(), Perbill::from_percent(60)));
+        // at least one vote is always required
+        assert_eq!(BridgeCommon::effective_threshold(), 1);
+
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(BridgeCommon::effective_threshold(), 2);
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::RelayerThresholdChanged(2)));
+
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_C));
+        assert_eq!(BridgeCommon::effective_threshold(), 2);
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::RelayerAdded(RELAYER_C)));
+
+        assert_ok!(BridgeCommon::remove_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(BridgeCommon::remove_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(BridgeCommon::effective_threshold(), 1);
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::RelayerThresholdChanged(1)));
+
+        // the last relayer can't leave
+        assert_noop!(
+            BridgeCommon::remove_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::ThresholdUnreachable
+        );
+
+        // an absolute threshold switches the ratio off
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_eq!(BridgeCommon::relayer_threshold_ratio(), None);
+        assert_eq!(BridgeCommon::effective_threshold(), TEST_THRESHOLD);
+    })
+}
+
+#[test]
+fn remove_relayer_keeps_threshold_reachable() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
+
+        assert_noop!(
+            BridgeCommon::remove_relayer(Origin::root(), RELAYER_B),
+            Error::<Test>::ThresholdUnreachable
+        );
+
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(BridgeCommon::remove_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(BridgeCommon::relayer_count(), 2);
+    })
+}
+
 #[test]
 fn setup_resources() {
     new_test_ext().execute_with(|| {
@@ -140,7 +212,6 @@ fn setup_resources() {
 #[test]
 fn add_remove_relayer() {
     new_test_ext().execute_with(|| {
-        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
         assert_eq!(BridgeCommon::relayer_count(), 0);
 
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
@@ -150,6 +221,7 @@ fn add_remove_relayer() {
             BadOrigin
         );
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_C));
         assert_eq!(BridgeCommon::relayer_count(), 3);
         assert_noop!(
@@ -178,6 +250,92 @@ fn add_remove_relayer() {
     })
 }
 
+#[test]
+fn relayer_set_is_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), FEES_ACCOUNT));
+        assert_eq!(BridgeCommon::relayer_count(), MaxRelayers::get());
+
+        Balances::make_free_balance_be(&42, ENDOWED_BALANCE);
+        assert_noop!(
+            BridgeCommon::add_relayer(Origin::root(), 42),
+            Error::<Test>::TooManyRelayers
+        );
+        assert_ok!(BridgeCommon::remove_relayer(Origin::root(), FEES_ACCOUNT));
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), 42));
+    })
+}
+
+#[test]
+fn admin_changes_apply_after_delay() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        AdminDelay::set(10);
+
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(
+            last_event(),
+            TestEvent::bridge_common(RawEvent::AdminActionAnnounced(0, AdminAction::AddRelayer(RELAYER_A), 11))
+        );
+        assert!(!BridgeCommon::is_relayer(&RELAYER_A));
+
+        // changes which couldn't apply now are refused up front
+        assert_noop!(BridgeCommon::set_threshold(Origin::root(), 0), Error::<Test>::InvalidThreshold);
+        assert_noop!(BridgeCommon::set_threshold(Origin::root(), 1), Error::<Test>::ThresholdUnreachable);
+        assert_noop!(BridgeCommon::remove_relayer(Origin::root(), RELAYER_A), Error::<Test>::RelayerInvalid);
+
+        // the admin cancels a pending change, a single guardian can't
+        assert_ok!(BridgeCommon::set_threshold_ratio(Origin::root(), Perbill::from_percent(50)));
+        assert_noop!(BridgeCommon::cancel_admin_action(Origin::signed(RELAYER_A), 1), BadOrigin);
+        assert_noop!(BridgeCommon::cancel_admin_action(Origin::signed(Guardian::get()), 1), BadOrigin);
+        assert_ok!(BridgeCommon::cancel_admin_action(Origin::root(), 1));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::AdminActionCancelled(1)));
+        assert_noop!(
+            BridgeCommon::cancel_admin_action(Origin::root(), 1),
+            Error::<Test>::AdminActionNotFound
+        );
+        assert_eq!(BridgeCommon::admin_action_queue(11), vec![0]);
+
+        // decimals and unfrozen nonces wait for the delay as well
+        let r_id = derive_resource_id(1, b"token");
+        assert_ok!(BridgeCommon::set_resource_decimals(Origin::root(), r_id, 15, 18));
+        assert_noop!(
+            BridgeCommon::unfreeze_proposal(Origin::root(), 1, 1, Default::default()),
+            Error::<Test>::ProposalNotFrozen
+        );
+        <FrozenNonces>::insert(1, 1, true);
+        assert_ok!(BridgeCommon::unfreeze_proposal(Origin::root(), 1, 1, Default::default()));
+        assert_eq!(BridgeCommon::admin_action_queue(11), vec![0, 2, 3]);
+        assert_eq!(BridgeCommon::resource_decimals(r_id), None);
+        assert!(BridgeCommon::frozen_nonces(1, 1));
+
+        BridgeCommon::on_initialize(10);
+        assert!(!BridgeCommon::is_relayer(&RELAYER_A));
+        BridgeCommon::on_initialize(11);
+        assert!(BridgeCommon::is_relayer(&RELAYER_A));
+        assert_eq!(BridgeCommon::resource_decimals(r_id), Some((15, 18)));
+        assert!(!BridgeCommon::frozen_nonces(1, 1));
+        assert_eq!(BridgeCommon::relayer_threshold(), DEFAULT_RELAYER_THRESHOLD);
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::AdminActionApplied(0)));
+        assert!(BridgeCommon::admin_actions(0).is_none());
+
+        // a change which no longer applies once its delay passed is dropped
+        System::set_block_number(2);
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+        Balances::make_free_balance_be(&RELAYER_B, 0);
+        BridgeCommon::on_initialize(12);
+        assert!(matches!(
+            last_event(),
+            TestEvent::bridge_common(RawEvent::AdminActionFailed(2, _))
+        ));
+        assert!(!BridgeCommon::is_relayer(&RELAYER_B));
+        assert_eq!(BridgeCommon::relayer_count(), 1);
+    })
+}
+
 #[test]
 fn map_resource_to_rsymbol_should_work() {
     new_test_ext().execute_with(|| {
@@ -212,6 +370,46 @@ fn set_proxy_accounts_should_work() {
 		assert_eq!(BridgeCommon::proxy_accounts(1), None);
 		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 1));
 		assert_eq!(BridgeCommon::proxy_accounts(1), Some(0));
+
+		// at most `MaxProxyAccounts` of them, counted once each
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 1));
+		for proxy in 2..=MaxProxyAccounts::get() as u64 {
+			assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), proxy));
+		}
+		assert_eq!(BridgeCommon::proxy_account_count(), MaxProxyAccounts::get());
+		assert_noop!(
+			BridgeCommon::set_proxy_accounts(Origin::root(), 42),
+			Error::<Test>::TooManyProxyAccounts,
+		);
+		assert_ok!(BridgeCommon::remove_proxy_accounts(Origin::root(), 1));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 42));
+	});
+}
+
+#[test]
+fn fee_schedules_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), 2));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 41));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 42));
+		for i in 0..MaxFeeSchedules::get() {
+			let r_id = derive_resource_id(1, &i.to_le_bytes());
+			assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(41), 2, r_id, FeeSchedule::default()));
+			assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(42), 2, r_id, FeeSchedule::default()));
+		}
+		assert_eq!(BridgeCommon::fee_submission_count(), MaxFeeSchedules::get());
+		assert_noop!(
+			BridgeCommon::submit_fee_schedule(Origin::signed(41), 2, derive_resource_id(1, b"token"), FeeSchedule::default()),
+			Error::<Test>::TooManyFeeSchedules,
+		);
+
+		// the pairs are freed once no proxy submitted for them
+		assert_ok!(BridgeCommon::remove_proxy_accounts(Origin::root(), 41));
+		assert_eq!(BridgeCommon::fee_submission_count(), MaxFeeSchedules::get());
+		assert_ok!(BridgeCommon::remove_proxy_accounts(Origin::root(), 42));
+		assert_eq!(BridgeCommon::fee_submission_count(), 0);
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 41));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(41), 2, derive_resource_id(1, b"token"), FeeSchedule::default()));
 	});
 }
 
@@ -232,21 +430,66 @@ fn remove_proxy_accounts_should_work() {
 
 
 #[test]
-fn set_chain_fees_should_work() {
+fn submit_fee_schedule_should_work() {
 	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let r_id = derive_resource_id(1, b"token");
+		let schedule = |flat: Balance, rate: u32, min: Balance, max: Balance| FeeSchedule {
+			flat,
+			rate: Permill::from_percent(rate),
+			min,
+			max,
+		};
+
 		assert_noop!(
-			BridgeCommon::set_chain_fees(Origin::signed(42), 2, 10),
+			BridgeCommon::submit_fee_schedule(Origin::signed(42), 2, r_id, schedule(10, 1, 5, 100)),
 			Error::<Test>::InvalidChainId,
 		);
 		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), 2));
 		assert_noop!(
-			BridgeCommon::set_chain_fees(Origin::signed(42), 2, 10),
+			BridgeCommon::submit_fee_schedule(Origin::signed(42), 2, r_id, schedule(10, 1, 5, 100)),
 			Error::<Test>::InvalidProxyAccount,
 		);
-		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 42));
-		assert_eq!(BridgeCommon::chain_fees(2), None);
-		assert_ok!(BridgeCommon::set_chain_fees(Origin::signed(42), 2, 10));
-		assert_eq!(BridgeCommon::chain_fees(2), Some(10));
+		for proxy in &[41, 42, 43] {
+			assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), *proxy));
+		}
+		assert_noop!(
+			BridgeCommon::submit_fee_schedule(Origin::signed(42), 2, r_id, schedule(10, 1, 100, 5)),
+			Error::<Test>::InvalidFeeSchedule,
+		);
+
+		// a single proxy can't set the fees on its own
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(41), 2, r_id, schedule(0, 0, 0, 0)));
+		assert_eq!(BridgeCommon::fee_schedules(2, r_id), None);
+		assert_eq!(BridgeCommon::quote_fee(2, r_id, 1_000), None);
+
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(42), 2, r_id, schedule(10, 1, 5, 100)));
+		assert_eq!(BridgeCommon::fee_schedules(2, r_id), Some(schedule(0, 0, 0, 0)));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(43), 2, r_id, schedule(1_000, 50, 1_000, 1_000_000)));
+		assert_eq!(
+			last_event(),
+			TestEvent::bridge_common(RawEvent::FeeScheduleSet(2, r_id, schedule(10, 1, 5, 100))),
+		);
+
+		// the outliers on both sides are ignored, there is no rate on a fee paid in AOW
+		assert_eq!(BridgeCommon::quote_fee(2, r_id, 1_000), Some((10, FeeCurrency::Native)));
+		assert_eq!(BridgeCommon::quote_fee(2, r_id, 0), Some((10, FeeCurrency::Native)));
+		assert_eq!(BridgeCommon::quote_fee(2, r_id, 100_000), Some((10, FeeCurrency::Native)));
+
+		// a new submission replaces the previous one of the same proxy
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(41), 2, r_id, schedule(20, 2, 10, 200)));
+		assert_eq!(BridgeCommon::fee_submissions(2, r_id).len(), 3);
+		assert_eq!(BridgeCommon::fee_schedules(2, r_id), Some(schedule(20, 2, 10, 200)));
+
+		// removed proxies no longer count
+		assert_ok!(BridgeCommon::remove_proxy_accounts(Origin::root(), 43));
+		assert_eq!(BridgeCommon::fee_submissions(2, r_id).len(), 2);
+		assert_eq!(BridgeCommon::fee_schedules(2, r_id), Some(schedule(10, 1, 5, 100)));
+
+		assert_noop!(BridgeCommon::set_fee_currency(Origin::signed(42), 2, r_id, FeeCurrency::Asset), BadOrigin);
+		assert_ok!(BridgeCommon::set_fee_currency(Origin::root(), 2, r_id, FeeCurrency::Asset));
+		assert_eq!(BridgeCommon::quote_fee(2, r_id, 1_000), Some((20, FeeCurrency::Asset)));
+		assert_eq!(BridgeCommon::quote_fee(2, r_id, 100_000), Some((100, FeeCurrency::Asset)));
 	});
 }
 
@@ -279,6 +522,112 @@ fn set_is_pasued_should_work() {
 	});
 }
 
+#[test]
+fn transfer_nonfungible_and_generic() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let dest_id = 2;
+        let rid = derive_resource_id(dest_id, &blake2_128(b"NFT"));
+        let to = vec![1, 2, 3];
+        let metadata = vec![7, 8];
+        let data = vec![4, 5, 6];
+
+        assert_noop!(
+            BridgeCommon::transfer_nonfungible(RELAYER_A, dest_id, rid, 9.into(), to.clone(), metadata.clone()),
+            Error::<Test>::ChainNotWhitelisted,
+        );
+        assert_noop!(
+            BridgeCommon::transfer_generic(RELAYER_A, dest_id, rid, data.clone()),
+            Error::<Test>::ChainNotWhitelisted,
+        );
+        assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), dest_id));
+
+        // both kinds share the outbound nonce of the destination chain
+        assert_ok!(BridgeCommon::transfer_nonfungible(RELAYER_A, dest_id, rid, 9.into(), to.clone(), metadata.clone()));
+        assert_eq!(
+            last_event(),
+            TestEvent::bridge_common(RawEvent::NonFungibleTransfer(RELAYER_A, dest_id, 1, rid, 9.into(), to, metadata)),
+        );
+        assert_ok!(BridgeCommon::transfer_generic(RELAYER_A, dest_id, rid, data.clone()));
+        assert_eq!(
+            last_event(),
+            TestEvent::bridge_common(RawEvent::GenericTransfer(RELAYER_A, dest_id, 2, rid, data)),
+        );
+        assert_eq!(BridgeCommon::chains(dest_id), Some(2));
+    })
+}
+
+#[test]
+fn deposit_records_are_kept_and_pruned() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        let dest_id = 2;
+        let rid = derive_resource_id(dest_id, &blake2_128(b"FIS"));
+        assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), dest_id));
+
+        assert_ok!(BridgeCommon::transfer_fungible(RELAYER_A, dest_id, rid, vec![1], 10.into()));
+        assert_eq!(
+            BridgeCommon::deposit_records(dest_id, 1),
+            Some(DepositRecord {
+                sender: RELAYER_A,
+                resource_id: rid,
+                data: DepositData::Fungible(10.into(), vec![1]),
+                block: 5,
+            }),
+        );
+
+        assert_ok!(BridgeCommon::transfer_nonfungible(RELAYER_B, dest_id, rid, 7.into(), vec![2], vec![3]));
+        assert_ok!(BridgeCommon::transfer_generic(RELAYER_C, dest_id, rid, vec![4]));
+        assert_eq!(
+            BridgeCommon::deposit_records(dest_id, 3).map(|r| r.data),
+            Some(DepositData::Generic(vec![4])),
+        );
+
+        // only the latest DepositRecordsRetention deposits are kept
+        assert_ok!(BridgeCommon::transfer_fungible(RELAYER_A, dest_id, rid, vec![5], 20.into()));
+        assert_eq!(BridgeCommon::deposit_records(dest_id, 1), None);
+        let nonces: Vec<DepositNonce> = BridgeCommon::deposit_records_from(dest_id, 0, 10)
+            .into_iter()
+            .map(|(nonce, _)| nonce)
+            .collect();
+        assert_eq!(nonces, vec![2, 3, 4]);
+        let nonces: Vec<DepositNonce> = BridgeCommon::deposit_records_from(dest_id, 3, 1)
+            .into_iter()
+            .map(|(nonce, _)| nonce)
+            .collect();
+        assert_eq!(nonces, vec![3]);
+    })
+}
+
+#[test]
+fn genesis_config_sets_up_bridge() {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    let r_id = derive_resource_id(2, b"remark");
+    GenesisConfig::<Test> {
+        fee_schedules: vec![(2, r_id, FeeSchedule { flat: 10, rate: Permill::zero(), min: 0, max: 10 })],
+        relayer_threshold: 2,
+        resources: vec![(r_id, b"System.remark".to_vec())],
+        relayers: vec![RELAYER_A, RELAYER_B, RELAYER_A],
+        chains: vec![2, 3],
+        address_formats: vec![(2, AddressFormat::Evm)],
+        resource_decimals: vec![],
+        fees_recipient: Some(FEES_ACCOUNT),
+    }.assimilate_storage(&mut t).unwrap();
+
+    sp_io::TestExternalities::from(t).execute_with(|| {
+        assert_eq!(BridgeCommon::relayer_count(), 2);
+        assert!(BridgeCommon::is_relayer(&RELAYER_A));
+        assert!(BridgeCommon::is_relayer(&RELAYER_B));
+        assert_eq!(BridgeCommon::relayer_threshold(), 2);
+        assert_eq!(BridgeCommon::chains(2), Some(0));
+        assert_eq!(BridgeCommon::chains(3), Some(0));
+        assert_eq!(BridgeCommon::quote_fee(2, r_id, 1_000), Some((10, FeeCurrency::Native)));
+        assert_eq!(BridgeCommon::address_formats(2), Some(AddressFormat::Evm));
+        assert_eq!(BridgeCommon::resources(r_id), Some(b"System.remark".to_vec()));
+        assert_eq!(BridgeCommon::fees_recipient_account(), Some(FEES_ACCOUNT));
+    })
+}
+
 fn make_proposal(r: Vec<u8>) -> mock::Call {
     Call::System(system::Call::remark(r))
 }
@@ -303,8 +652,10 @@ fn create_sucessful_proposal() {
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = ProposalVotes {
             voted: vec![RELAYER_A],
+            votes_against: vec![],
             status: ProposalStatus::Active,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
@@ -319,13 +670,38 @@ fn create_sucessful_proposal() {
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = ProposalVotes {
             voted: vec![RELAYER_A, RELAYER_C],
+            votes_against: vec![],
             status: ProposalStatus::Executed,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
     })
 }
 
+#[test]
+fn proposal_outside_filter_is_refused() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = Call::BridgeCommon(crate::Call::set_threshold(1));
+
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalNotAllowed
+        );
+        assert_eq!(BridgeCommon::votes(src_id, (1, proposal)), None);
+        assert_eq!(BridgeCommon::relayer_threshold(), 2);
+    })
+}
+
 #[test]
 fn proposal_expires_should_work() {
     let src_id = 2;
@@ -346,8 +722,10 @@ fn proposal_expires_should_work() {
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = ProposalVotes {
             voted: vec![RELAYER_A],
+            votes_against: vec![],
             status: ProposalStatus::Active,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
@@ -373,13 +751,1052 @@ fn proposal_expires_should_work() {
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = ProposalVotes {
             voted: vec![RELAYER_A],
+            votes_against: vec![],
             status: ProposalStatus::Expired,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: r_id,
+        };
+        assert_eq!(prop, expected);
+    })
+}
+
+#[test]
+fn create_unsuccessful_proposal() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        System::set_block_number(1);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![11]);
+
+        // Only relayers can vote against
+        assert_noop!(
+            BridgeCommon::reject_proposal(
+                Origin::signed(42),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::MustBeRelayer
+        );
+
+        // Create proposal (& vote)
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        // Second relayer votes against, threshold is still reachable
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            voted: vec![RELAYER_A],
+            votes_against: vec![RELAYER_B],
+            status: ProposalStatus::Active,
+            expiry: ProposalLifetime::get() as u64 + 1,
+            resource_id: r_id,
+        };
+        assert_eq!(prop, expected);
+
+        // Second relayer can't change its mind
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::RelayerAlreadyVoted
+        );
+
+        // Third relayer votes against
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            voted: vec![RELAYER_A],
+            votes_against: vec![RELAYER_B, RELAYER_C],
+            status: ProposalStatus::Rejected,
+            expiry: ProposalLifetime::get() as u64 + 1,
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
+        assert_eq!(
+            last_event(),
+            TestEvent::bridge_common(RawEvent::ProposalCancelled(src_id, prop_id))
+        );
+    })
+}
+
+#[test]
+fn rejected_proposal_releases_nonce() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![13]);
+        let other = make_proposal(vec![14]);
+        let hash = <Test as system::Trait>::Hashing::hash_of(&proposal);
+
+        // Votes against don't claim the nonce
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(other.clone())
+        ));
+        assert_eq!(BridgeCommon::nonce_proposal(src_id, prop_id), None);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(BridgeCommon::nonce_proposal(src_id, prop_id), Some(hash));
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(BridgeCommon::nonce_proposal(src_id, prop_id), None);
+        assert_eq!(BridgeCommon::nonce_voters(src_id, prop_id).is_empty(), true);
+
+        // Another call can be proposed for the nonce without freezing it
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(other.clone())
+        ));
+        assert_eq!(BridgeCommon::frozen_nonces(src_id, prop_id), false);
     })
 }
 
-// fn last_event() -> TestEvent {
-// 	system::Module::<Test>::events().pop().map(|e| e.event).expect("Event expected")
-// }
\ No newline at end of file
+#[test]
+fn rejected_proposal_cannot_be_voted() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![12]);
+
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Rejected);
+
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalAlreadyCompleted
+        );
+    })
+}
+
+#[test]
+fn sweeper_expires_and_prunes_proposals() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        System::set_block_number(1);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![13]);
+        let expiry = ProposalLifetime::get() as u64 + 1;
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(BridgeCommon::expiry_queue(expiry).len(), 1);
+
+        // Nothing happens before expiry
+        BridgeCommon::on_initialize(expiry - 1);
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Active);
+
+        System::set_block_number(expiry);
+        BridgeCommon::on_initialize(expiry);
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Expired);
+        assert_eq!(
+            last_event(),
+            TestEvent::bridge_common(RawEvent::ProposalCancelled(src_id, prop_id))
+        );
+        assert_eq!(BridgeCommon::expiry_queue(expiry).is_empty(), true);
+
+        let prune_at = expiry + VotesRetention::get() as u64;
+        assert_eq!(BridgeCommon::prune_queue(prune_at).len(), 1);
+        BridgeCommon::on_initialize(prune_at);
+        assert_eq!(BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())), None);
+        assert_eq!(BridgeCommon::prune_queue(prune_at).is_empty(), true);
+    })
+}
+
+#[test]
+fn sweeper_prunes_executed_proposals() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![14]);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Executed);
+
+        let prune_at = VotesRetention::get() as u64;
+        assert_eq!(BridgeCommon::prune_queue(prune_at).len(), 1);
+        BridgeCommon::on_initialize(prune_at);
+        assert_eq!(BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())), None);
+
+        // Reaching expiry afterwards is a no-op
+        let expiry = ProposalLifetime::get() as u64;
+        BridgeCommon::on_initialize(expiry);
+        assert_eq!(BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())), None);
+        assert_eq!(BridgeCommon::expiry_queue(expiry).is_empty(), true);
+    })
+}
+
+#[test]
+fn sweeper_is_bounded_per_block() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let expiry = ProposalLifetime::get() as u64;
+        for i in 0..3u64 {
+            assert_ok!(BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                i,
+                src_id,
+                r_id,
+                Box::new(make_proposal(vec![i as u8]))
+            ));
+        }
+        assert_eq!(BridgeCommon::expiry_queue(expiry).len(), 3);
+
+        // Only `MaxSweepPerBlock` proposals are handled, the rest is carried over
+        BridgeCommon::on_initialize(expiry);
+        assert_eq!(BridgeCommon::expiry_queue(expiry + 1).len(), 1);
+        let prop = BridgeCommon::votes(src_id, (2, make_proposal(vec![2]))).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Active);
+
+        BridgeCommon::on_initialize(expiry + 1);
+        let prop = BridgeCommon::votes(src_id, (2, make_proposal(vec![2]))).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Expired);
+        assert_eq!(BridgeCommon::expiry_queue(expiry + 1).is_empty(), true);
+    })
+}
+
+#[test]
+fn conflicting_proposals_freeze_nonce() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        System::set_block_number(1);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![15]);
+        let bogus = make_proposal(vec![16]);
+        let hash = <Test as system::Trait>::Hashing::hash_of(&proposal);
+        let bogus_hash = <Test as system::Trait>::Hashing::hash_of(&bogus);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(BridgeCommon::nonce_proposal(src_id, prop_id), Some(hash));
+
+        // Another call for the same nonce freezes it
+        assert_err!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(bogus.clone())
+            ),
+            Error::<Test>::ProposalFrozen
+        );
+        assert_eq!(BridgeCommon::frozen_nonces(src_id, prop_id), true);
+        assert_eq!(
+            last_event(),
+            TestEvent::bridge_common(RawEvent::ProposalConflict(src_id, prop_id, hash, bogus_hash))
+        );
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalFrozen
+        );
+
+        // Admin picks the right call
+        assert_noop!(
+            BridgeCommon::unfreeze_proposal(Origin::signed(42), src_id, prop_id, hash),
+            BadOrigin
+        );
+        assert_ok!(BridgeCommon::unfreeze_proposal(Origin::root(), src_id, prop_id, hash));
+        assert_noop!(
+            BridgeCommon::unfreeze_proposal(Origin::root(), src_id, prop_id, hash),
+            Error::<Test>::ProposalNotFrozen
+        );
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Executed);
+        assert_eq!(BridgeCommon::executed_nonces(src_id, prop_id), Some(hash));
+        assert_eq!(BridgeCommon::nonce_proposal(src_id, prop_id), None);
+
+        // The nonce can't be used by any other call anymore
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(bogus.clone())
+            ),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+    })
+}
+
+#[test]
+fn executed_nonce_cannot_be_replayed_after_pruning() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![17]);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        BridgeCommon::on_initialize(VotesRetention::get() as u64);
+        assert_eq!(BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())), None);
+
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+    })
+}
+
+#[test]
+fn executed_nonces_are_kept_within_window() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let execute = |nonce: DepositNonce, proposal: Call| {
+            for relayer in &[RELAYER_A, RELAYER_B] {
+                assert_ok!(BridgeCommon::acknowledge_proposal(
+                    Origin::signed(*relayer),
+                    nonce,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ));
+            }
+        };
+
+        execute(1, make_proposal(vec![30]));
+        execute(2, make_proposal(vec![31]));
+        assert_eq!(BridgeCommon::executed_nonces_mark(src_id), 2);
+
+        // Nonce 5 moves the window past nonces 1 and 2
+        execute(5, make_proposal(vec![32]));
+        assert_eq!(BridgeCommon::executed_nonces_mark(src_id), 5);
+        assert_eq!(BridgeCommon::executed_nonces(src_id, 1), None);
+        assert_eq!(BridgeCommon::executed_nonces(src_id, 2), None);
+        assert!(BridgeCommon::executed_nonces(src_id, 5).is_some());
+
+        // They still count as executed
+        assert!(BridgeCommon::nonce_executed(src_id, 1));
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                2,
+                src_id,
+                r_id,
+                Box::new(make_proposal(vec![33]))
+            ),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+
+        // Nonces within the window are still accepted out of order
+        assert!(!BridgeCommon::nonce_executed(src_id, 3));
+        execute(3, make_proposal(vec![34]));
+        assert!(BridgeCommon::executed_nonces(src_id, 3).is_some());
+        assert_eq!(BridgeCommon::executed_nonces_mark(src_id), 5);
+    })
+}
+
+#[test]
+fn relayer_bond_is_reserved_and_released() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_A), RelayerBond::get());
+        assert_eq!(Balances::reserved_balance(RELAYER_A), RelayerBond::get());
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE - RelayerBond::get());
+
+        // Can't join without the bond
+        assert_noop!(
+            BridgeCommon::add_relayer(Origin::root(), 42),
+            pallet_balances::Error::<Test, _>::InsufficientBalance
+        );
+
+        assert_ok!(BridgeCommon::remove_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_A), 0);
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 0);
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE);
+    })
+}
+
+#[test]
+fn executed_proposal_rewards_relayers() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![18]);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        let share = RelayerReward::get() / 2;
+        assert_eq!(BridgeCommon::relayer_rewards(RELAYER_A), share);
+        assert_eq!(BridgeCommon::relayer_rewards(RELAYER_B), 0);
+        assert_eq!(BridgeCommon::relayer_rewards(RELAYER_C), share);
+
+        assert_noop!(
+            BridgeCommon::claim_rewards(Origin::signed(RELAYER_B)),
+            Error::<Test>::NoRewards
+        );
+        assert_noop!(
+            BridgeCommon::claim_rewards(Origin::signed(RELAYER_A)),
+            Error::<Test>::FeesRecipientAccountNotSet
+        );
+
+        assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), FEES_ACCOUNT));
+        let free = Balances::free_balance(RELAYER_A);
+        assert_ok!(BridgeCommon::claim_rewards(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::free_balance(RELAYER_A), free + share);
+        assert_eq!(Balances::free_balance(FEES_ACCOUNT), ENDOWED_BALANCE - share);
+        assert_eq!(BridgeCommon::relayer_rewards(RELAYER_A), 0);
+    })
+}
+
+#[test]
+fn relayers_are_slashed_for_rejected_proposal() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![19]);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(BridgeCommon::reject_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        let bond = RelayerBond::get() - RelayerSlash::get();
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_A), bond);
+        assert_eq!(Balances::reserved_balance(RELAYER_A), bond);
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_B), RelayerBond::get());
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_C), RelayerBond::get());
+    })
+}
+
+#[test]
+fn expiries_while_paused_are_not_slashed() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        System::set_block_number(1);
+        let expiry = ProposalLifetime::get() as u64 + 1;
+        let paused = make_proposal(vec![21]);
+        let unpaused = make_proposal(vec![22]);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, Box::new(paused.clone())));
+
+        // the pause refuses the votes which would have carried the proposal
+        assert_ok!(BridgeCommon::pause_resource(Origin::signed(Guardian::get()), r_id));
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_C), 1, src_id, r_id, Box::new(paused.clone())),
+            Error::<Test>::ResourcePaused
+        );
+        System::set_block_number(expiry);
+        BridgeCommon::on_initialize(expiry);
+        assert_eq!(BridgeCommon::votes(src_id, (1, paused)).map(|v| v.status), Some(ProposalStatus::Expired));
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_A), RelayerBond::get());
+
+        // once unpaused, proposals left to expire are slashed again
+        assert_ok!(BridgeCommon::unpause_resource(Origin::root(), r_id));
+        let expiry = expiry + ProposalLifetime::get() as u64;
+        assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_B), 2, src_id, r_id, Box::new(unpaused)));
+        System::set_block_number(expiry);
+        BridgeCommon::on_initialize(expiry);
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_B), RelayerBond::get() - RelayerSlash::get());
+    })
+}
+
+#[test]
+fn relayers_are_slashed_for_conflicting_proposal() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![20]);
+        let bogus = make_proposal(vec![21]);
+        let hash = <Test as system::Trait>::Hashing::hash_of(&proposal);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_err!(
+            BridgeCommon::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(bogus.clone())
+            ),
+            Error::<Test>::ProposalFrozen
+        );
+        assert_ok!(BridgeCommon::unfreeze_proposal(Origin::root(), src_id, prop_id, hash));
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_A), RelayerBond::get());
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_B), RelayerBond::get() - RelayerSlash::get());
+        assert_eq!(BridgeCommon::relayer_bonds(RELAYER_C), RelayerBond::get());
+        assert_eq!(BridgeCommon::nonce_voters(src_id, prop_id).is_empty(), true);
+    })
+}
+
+#[test]
+fn query_proposals_and_chains() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let executed = make_proposal(vec![22]);
+        let pending = make_proposal(vec![23]);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            Box::new(executed.clone())
+        ));
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            src_id,
+            r_id,
+            Box::new(executed.clone())
+        ));
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            src_id,
+            r_id,
+            Box::new(pending.clone())
+        ));
+
+        let proposals = BridgeCommon::pending_proposals(src_id);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].0, 2);
+        assert_eq!(proposals[0].1, pending);
+        assert_eq!(proposals[0].2.voted, vec![RELAYER_A]);
+        assert_eq!(BridgeCommon::pending_proposals(3).is_empty(), true);
+
+        let proposals = BridgeCommon::nonce_proposals(src_id, 1);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].0, executed);
+        assert_eq!(proposals[0].1.status, ProposalStatus::Executed);
+
+        assert_eq!(BridgeCommon::whitelisted_chains(), vec![(src_id, 0)]);
+    })
+}
+
+fn last_event() -> TestEvent {
+    system::Module::<Test>::events().pop().map(|e| e.event).expect("Event expected")
+}
+
+#[test]
+fn guardian_pauses_chains_and_resources() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        System::set_block_number(1);
+        let guardian = Guardian::get();
+        let proposal = make_proposal(vec![10]);
+
+        assert_noop!(BridgeCommon::pause_chain(Origin::signed(RELAYER_A), src_id), BadOrigin);
+        assert_ok!(BridgeCommon::pause_chain(Origin::signed(guardian), src_id));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ChainPaused(src_id)));
+
+        // both directions of the route are stopped
+        assert_noop!(
+            BridgeCommon::transfer_fungible(RELAYER_A, src_id, r_id, vec![1], 10.into()),
+            Error::<Test>::ChainPaused,
+        );
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, Box::new(proposal.clone())),
+            Error::<Test>::ChainPaused,
+        );
+
+        // the guardian can't lift the pause
+        assert_noop!(BridgeCommon::unpause_chain(Origin::signed(guardian), src_id), BadOrigin);
+        assert_ok!(BridgeCommon::unpause_chain(Origin::root(), src_id));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ChainUnpaused(src_id)));
+
+        assert_ok!(BridgeCommon::pause_resource(Origin::signed(guardian), r_id));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ResourcePaused(r_id)));
+        assert_noop!(
+            BridgeCommon::transfer_generic(RELAYER_A, src_id, r_id, vec![1]),
+            Error::<Test>::ResourcePaused,
+        );
+        assert_noop!(
+            BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, Box::new(proposal.clone())),
+            Error::<Test>::ResourcePaused,
+        );
+        // other resources of the chain are unaffected
+        let other = derive_resource_id(src_id, b"other");
+        assert_ok!(BridgeCommon::transfer_generic(RELAYER_A, src_id, other, vec![1]));
+
+        assert_noop!(BridgeCommon::unpause_resource(Origin::signed(guardian), r_id), BadOrigin);
+        assert_ok!(BridgeCommon::unpause_resource(Origin::root(), r_id));
+        assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, Box::new(proposal)));
+    })
+}
+
+#[test]
+fn address_formats_validate_recipients() {
+    let evm = AddressFormat::Evm;
+    assert!(evm.is_valid(&[1; 20]));
+    assert!(!evm.is_valid(&[1; 32]));
+
+    // Alice on the generic substrate network
+    let ss58 = AddressFormat::Ss58(42);
+    assert!(ss58.is_valid(b"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"));
+    assert!(!ss58.is_valid(b"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"));
+    assert!(!AddressFormat::Ss58(0).is_valid(b"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"));
+    assert!(!ss58.is_valid(&[1; 32]));
+
+    let bech32 = AddressFormat::Bech32(b"bc".to_vec());
+    assert!(bech32.is_valid(b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+    assert!(!bech32.is_valid(b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"));
+    assert!(!bech32.is_valid(b"BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"));
+    assert!(!AddressFormat::Bech32(b"tb".to_vec()).is_valid(b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+}
+
+#[test]
+fn recipients_are_checked_against_the_chain_format() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let dest_id = 2;
+
+        assert_noop!(BridgeCommon::ensure_valid_recipient(dest_id, &[1; 20]), Error::<Test>::AddressFormatNotSet);
+
+        assert_noop!(BridgeCommon::set_address_format(Origin::signed(RELAYER_A), dest_id, AddressFormat::Evm), BadOrigin);
+        assert_ok!(BridgeCommon::set_address_format(Origin::root(), dest_id, AddressFormat::Evm));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::AddressFormatSet(dest_id, AddressFormat::Evm)));
+        assert_ok!(BridgeCommon::ensure_valid_recipient(dest_id, &[1; 20]));
+        assert_noop!(BridgeCommon::ensure_valid_recipient(dest_id, &[1; 19]), Error::<Test>::InvalidRecipient);
+
+        assert_ok!(BridgeCommon::remove_address_format(Origin::root(), dest_id));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::AddressFormatRemoved(dest_id)));
+        assert_noop!(BridgeCommon::remove_address_format(Origin::root(), dest_id), Error::<Test>::AddressFormatNotSet);
+        assert_noop!(BridgeCommon::ensure_valid_recipient(dest_id, &[1; 20]), Error::<Test>::AddressFormatNotSet);
+    })
+}
+
+#[test]
+fn amounts_are_scaled_to_resource_decimals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let r_id = derive_resource_id(1, b"token");
+
+        // taken as is until decimals are set
+        assert_eq!(BridgeCommon::to_foreign_amount(r_id, 1_234), Ok(U256::from(1_234)));
+        assert_eq!(BridgeCommon::to_local_amount(r_id, U256::from(1_234)), Ok(1_234));
+
+        assert_noop!(BridgeCommon::set_resource_decimals(Origin::signed(RELAYER_A), r_id, 15, 18), BadOrigin);
+        assert_ok!(BridgeCommon::set_resource_decimals(Origin::root(), r_id, 15, 18));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ResourceDecimalsSet(r_id, 15, 18)));
+
+        assert_eq!(BridgeCommon::to_foreign_amount(r_id, 1_234), Ok(U256::from(1_234_000)));
+        assert_eq!(BridgeCommon::to_local_amount(r_id, U256::from(1_234_000)), Ok(1_234));
+        // dust of the foreign units is refused instead of truncated
+        assert_noop!(BridgeCommon::to_local_amount(r_id, U256::from(1_234_001)), Error::<Test>::AmountNotRepresentable);
+        assert_noop!(BridgeCommon::to_local_amount(r_id, U256::max_value()), Error::<Test>::AmountOverflow);
+
+        // the other way around
+        assert_ok!(BridgeCommon::set_resource_decimals(Origin::root(), r_id, 18, 6));
+        assert_eq!(BridgeCommon::to_foreign_amount(r_id, 5_000_000_000_000), Ok(U256::from(5)));
+        assert_noop!(BridgeCommon::to_foreign_amount(r_id, 5_000_000_000_001), Error::<Test>::AmountNotRepresentable);
+        assert_eq!(BridgeCommon::to_local_amount(r_id, U256::from(5)), Ok(5_000_000_000_000));
+    })
+}
+
+#[test]
+fn failed_proposals_can_be_retried_or_refunded() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"Balances.transfer".to_vec()).execute_with(|| {
+        System::set_block_number(1);
+        let bridge_id = BridgeCommon::account_id();
+        // the bridge account can't pay yet
+        let proposal = Call::Balances(pallet_balances::Call::transfer(RELAYER_A, 10));
+        let insufficient: DispatchError = pallet_balances::Error::<Test, _>::InsufficientBalance.into();
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, Box::new(proposal.clone())));
+        assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, Box::new(proposal.clone())));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ProposalFailed(src_id, 1, insufficient)));
+        assert_eq!(BridgeCommon::votes(src_id, (1, proposal.clone())).unwrap().status, ProposalStatus::Failed);
+        assert_eq!(BridgeCommon::failed_proposals(src_id, 1), Some((r_id, insufficient)));
+        assert_eq!(BridgeCommon::executed_nonces(src_id, 1), None);
+
+        // no more votes, only retries
+        assert_err!(
+            BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_C), 1, src_id, r_id, Box::new(proposal.clone())),
+            Error::<Test>::ProposalAlreadyCompleted,
+        );
+        assert_noop!(BridgeCommon::retry_proposal(Origin::signed(FEES_ACCOUNT), src_id, 1, Box::new(proposal.clone())), BadOrigin);
+        assert_ok!(BridgeCommon::retry_proposal(Origin::signed(RELAYER_C), src_id, 1, Box::new(proposal.clone())));
+        assert_eq!(BridgeCommon::votes(src_id, (1, proposal.clone())).unwrap().status, ProposalStatus::Failed);
+
+        // nor while the route is paused
+        assert_ok!(Balances::transfer(Origin::signed(FEES_ACCOUNT), bridge_id, 50));
+        assert_ok!(BridgeCommon::pause_resource(Origin::signed(Guardian::get()), r_id));
+        assert_noop!(
+            BridgeCommon::retry_proposal(Origin::signed(RELAYER_C), src_id, 1, Box::new(proposal.clone())),
+            Error::<Test>::ResourcePaused,
+        );
+        assert_ok!(BridgeCommon::unpause_resource(Origin::root(), r_id));
+        assert_ok!(BridgeCommon::pause_chain(Origin::signed(Guardian::get()), src_id));
+        assert_noop!(
+            BridgeCommon::retry_proposal(Origin::signed(RELAYER_C), src_id, 1, Box::new(proposal.clone())),
+            Error::<Test>::ChainPaused,
+        );
+        assert_ok!(BridgeCommon::unpause_chain(Origin::root(), src_id));
+        assert_ok!(BridgeCommon::retry_proposal(Origin::signed(RELAYER_C), src_id, 1, Box::new(proposal.clone())));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ProposalExecuted(src_id, 1)));
+        assert_eq!(BridgeCommon::votes(src_id, (1, proposal.clone())).unwrap().status, ProposalStatus::Executed);
+        assert_eq!(BridgeCommon::failed_proposals(src_id, 1), None);
+        assert!(BridgeCommon::executed_nonces(src_id, 1).is_some());
+        assert_noop!(
+            BridgeCommon::retry_proposal(Origin::root(), src_id, 1, Box::new(proposal.clone())),
+            Error::<Test>::ProposalNotFailed,
+        );
+
+        // a proposal which can't go through is sent back to the depositor instead
+        let proposal = Call::Balances(pallet_balances::Call::transfer(RELAYER_A, 1_000));
+        assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_A), 2, src_id, r_id, Box::new(proposal.clone())));
+        assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_B), 2, src_id, r_id, Box::new(proposal.clone())));
+        assert_eq!(BridgeCommon::votes(src_id, (2, proposal.clone())).unwrap().status, ProposalStatus::Failed);
+
+        assert_noop!(
+            BridgeCommon::refund_proposal(Origin::signed(RELAYER_A), src_id, 2, Box::new(proposal.clone())),
+            BadOrigin,
+        );
+        // the relayers have to agree on the deposit first
+        assert_noop!(
+            BridgeCommon::refund_proposal(Origin::root(), src_id, 2, Box::new(proposal.clone())),
+            Error::<Test>::SourceDepositUnknown,
+        );
+        assert_ok!(BridgeCommon::set_address_format(Origin::root(), src_id, AddressFormat::Evm));
+        let depositor = vec![7u8; 20];
+        let deposit = SourceDeposit { resource_id: r_id, depositor: depositor.clone(), amount: 1_000.into() };
+        let bogus = SourceDeposit { depositor: vec![8u8; 20], ..deposit.clone() };
+        assert_noop!(
+            BridgeCommon::attest_source_deposit(Origin::signed(FEES_ACCOUNT), src_id, 2, deposit.clone()),
+            Error::<Test>::MustBeRelayer,
+        );
+        assert_noop!(
+            BridgeCommon::attest_source_deposit(Origin::signed(RELAYER_A), src_id, 2, SourceDeposit { depositor: vec![1], ..deposit.clone() }),
+            Error::<Test>::InvalidRecipient,
+        );
+        assert_ok!(BridgeCommon::attest_source_deposit(Origin::signed(RELAYER_A), src_id, 2, deposit.clone()));
+        assert_noop!(
+            BridgeCommon::attest_source_deposit(Origin::signed(RELAYER_A), src_id, 2, deposit.clone()),
+            Error::<Test>::RelayerAlreadyVoted,
+        );
+        assert_ok!(BridgeCommon::attest_source_deposit(Origin::signed(RELAYER_B), src_id, 2, bogus));
+        assert_eq!(BridgeCommon::source_deposits(src_id, 2), None);
+        assert_ok!(BridgeCommon::attest_source_deposit(Origin::signed(RELAYER_C), src_id, 2, deposit.clone()));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::SourceDepositAttested(src_id, 2)));
+        assert_eq!(BridgeCommon::source_deposits(src_id, 2), Some(deposit));
+
+        let rewards = BridgeCommon::relayer_rewards(RELAYER_A);
+        assert_ok!(BridgeCommon::refund_proposal(Origin::root(), src_id, 2, Box::new(proposal.clone())));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ProposalRefunded(src_id, 2)));
+        assert_eq!(BridgeCommon::votes(src_id, (2, proposal.clone())).unwrap().status, ProposalStatus::Refunded);
+        assert!(BridgeCommon::executed_nonces(src_id, 2).is_some());
+        assert_eq!(BridgeCommon::source_deposits(src_id, 2), None);
+        assert_eq!(
+            BridgeCommon::deposit_records(src_id, 1).map(|r| r.data),
+            Some(DepositData::Fungible(1_000.into(), depositor)),
+        );
+        // voting for a call which never went through isn't rewarded
+        assert_eq!(BridgeCommon::relayer_rewards(RELAYER_A), rewards);
+    })
+}
+
+#[test]
+fn signed_proposals_execute_in_one_extrinsic() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        System::set_block_number(1);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let key_a = sp_core::sr25519::Pair::from_string("//RelayerA", None).unwrap();
+        let key_b = sp_core::ecdsa::Pair::from_string("//RelayerB", None).unwrap();
+        let msg = BridgeCommon::proposal_message(src_id, prop_id, r_id, &proposal);
+        let sig_a = RelayerSignature::Sr25519(key_a.sign(&msg));
+        let sig_b = RelayerSignature::Ecdsa(key_b.sign(&msg));
+        let submit = |signatures| BridgeCommon::submit_signed_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone()),
+            signatures,
+        );
+
+        // only relayers register keys
+        assert_noop!(
+            BridgeCommon::set_relayer_key(Origin::signed(FEES_ACCOUNT), RelayerKey::Sr25519(key_a.public())),
+            Error::<Test>::MustBeRelayer
+        );
+        assert_noop!(submit(vec![(RELAYER_A, sig_a.clone())]), Error::<Test>::RelayerKeyNotSet);
+        assert_ok!(BridgeCommon::set_relayer_key(Origin::signed(RELAYER_A), RelayerKey::Sr25519(key_a.public())));
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::RelayerKeySet(RELAYER_A)));
+        assert_ok!(BridgeCommon::set_relayer_key(Origin::signed(RELAYER_B), RelayerKey::Ecdsa(key_b.public())));
+
+        // signatures must come from distinct relayers, over this very proposal
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone()), (RELAYER_A, sig_a.clone())]),
+            Error::<Test>::DuplicateSignature
+        );
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone()), (RELAYER_B, sig_a.clone())]),
+            Error::<Test>::InvalidSignature
+        );
+        let other = BridgeCommon::proposal_message(src_id, prop_id + 1, r_id, &proposal);
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone()), (RELAYER_B, RelayerSignature::Ecdsa(key_b.sign(&other)))]),
+            Error::<Test>::InvalidSignature
+        );
+        // nor over the proposal without the domain and the identity of this chain
+        let hash = <Test as frame_system::Trait>::Hashing::hash_of(&proposal);
+        let bare = (src_id, prop_id, r_id, hash).encode();
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone()), (RELAYER_B, RelayerSignature::Ecdsa(key_b.sign(&bare)))]),
+            Error::<Test>::InvalidSignature
+        );
+        let mut expected = b"bridge-common:proposal".to_vec();
+        (ChainIdentity::get(), src_id, prop_id, r_id, hash).encode_to(&mut expected);
+        assert_eq!(msg, expected);
+        assert_noop!(submit(vec![(FEES_ACCOUNT, sig_a.clone())]), Error::<Test>::RelayerInvalid);
+        assert_noop!(submit(vec![(RELAYER_A, sig_a.clone())]), Error::<Test>::NotEnoughSignatures);
+
+        assert_ok!(submit(vec![(RELAYER_A, sig_a.clone()), (RELAYER_B, sig_b.clone())]));
+        let prop = BridgeCommon::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.voted, vec![RELAYER_A, RELAYER_B]);
+        assert_eq!(prop.status, ProposalStatus::Executed);
+        assert_eq!(last_event(), TestEvent::bridge_common(RawEvent::ProposalExecuted(src_id, prop_id)));
+
+        // removed relayers lose their key
+        assert_ok!(BridgeCommon::remove_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(BridgeCommon::relayer_keys(RELAYER_B), None);
+    })
+}
+
+#[test]
+fn signed_proposals_count_earlier_votes() {
+    let src_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let key_a = sp_core::sr25519::Pair::from_string("//RelayerA", None).unwrap();
+        let key_c = sp_core::sr25519::Pair::from_string("//RelayerC", None).unwrap();
+        assert_ok!(BridgeCommon::set_relayer_key(Origin::signed(RELAYER_A), RelayerKey::Sr25519(key_a.public())));
+        assert_ok!(BridgeCommon::set_relayer_key(Origin::signed(RELAYER_C), RelayerKey::Sr25519(key_c.public())));
+        let msg = BridgeCommon::proposal_message(src_id, prop_id, r_id, &proposal);
+
+        assert_ok!(BridgeCommon::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        // relayer A already voted, its signature is skipped
+        assert_ok!(BridgeCommon::submit_signed_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone()),
+            vec![
+                (RELAYER_A, RelayerSignature::Sr25519(key_a.sign(&msg))),
+                (RELAYER_C, RelayerSignature::Sr25519(key_c.sign(&msg))),
+            ],
+        ));
+        let prop = BridgeCommon::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.voted, vec![RELAYER_A, RELAYER_C]);
+        assert_eq!(prop.status, ProposalStatus::Executed);
+    })
+}
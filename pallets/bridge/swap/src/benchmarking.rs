@@ -0,0 +1,208 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bridge swap pallet benchmarking.
+//!
+//! `transfer_nonfungible`, `transfer_nonfungible_back` and `execute_generic` are left out,
+//! most of their work is done by the handlers the runtime plugs in.
+
+use super::*;
+use crate::Module as BridgeSwap;
+
+use frame_system::RawOrigin;
+use frame_support::{StorageValue, StorageMap, StorageDoubleMap};
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use bridge::{AddressFormat, FeeSchedule};
+
+const SEED: u32 = 0;
+const DEST_ID: ChainId = 2;
+const SYMBOL: RSymbol = RSymbol::RFIS;
+
+fn rtoken_resource() -> ResourceId {
+    bridge::derive_resource_id(DEST_ID, b"rtoken")
+}
+
+fn generic_resource() -> ResourceId {
+    bridge::derive_resource_id(DEST_ID, b"generic")
+}
+
+fn native_amount<T: Trait>() -> BalanceOf<T> {
+    <T as Trait>::Currency::minimum_balance().saturating_mul(100u32.into())
+}
+
+fn fund<T: Trait>(who: &T::AccountId) {
+    <T as Trait>::Currency::make_free_balance_be(who, native_amount::<T>().saturating_mul(10u32.into()));
+}
+
+/// Whitelists `DEST_ID` with fees and limits on every resource, maps the rtoken resource to `SYMBOL`.
+/// Delayed admin changes are written directly.
+fn setup_bridge<T: Trait>() -> Result<(), &'static str> {
+    let admin: T::Origin = RawOrigin::Root.into();
+    <bridge::Module<T>>::whitelist_chain(admin.clone(), DEST_ID)?;
+    <bridge::Module<T>>::set_address_format(admin.clone(), DEST_ID, AddressFormat::Evm)?;
+
+    let fees_recipient: T::AccountId = account("fees", 0, SEED);
+    fund::<T>(&fees_recipient);
+    bridge::FeesRecipientAccount::<T>::put(fees_recipient);
+
+    let fee: u128 = <T as Trait>::Currency::minimum_balance().saturated_into();
+    let schedule = FeeSchedule { flat: fee, min: fee, max: fee, ..Default::default() };
+    bridge::ResourceRsymbol::insert(rtoken_resource(), SYMBOL);
+    bridge::RsymbolResource::insert(SYMBOL, rtoken_resource());
+    for resource_id in vec![T::NativeTokenId::get(), rtoken_resource(), generic_resource()] {
+        bridge::FeeSchedules::insert(DEST_ID, resource_id, schedule);
+        bridge::ResourceDecimals::insert(resource_id, (12, 18));
+        BridgeSwap::<T>::set_transfer_limit(admin.clone(), resource_id, 0, u128::max_value(), u128::max_value())?;
+    }
+    Ok(())
+}
+
+fn bridge_origin<T: Trait>() -> RawOrigin<T::AccountId> {
+    RawOrigin::Signed(<bridge::Module<T>>::account_id())
+}
+
+benchmarks! {
+    _ { }
+
+    transfer_native {
+        setup_bridge::<T>()?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), native_amount::<T>(), vec![1; 20], DEST_ID)
+    verify {
+        assert_eq!(BridgeSwap::<T>::locked_supply(T::NativeTokenId::get()), native_amount::<T>().saturated_into());
+    }
+
+    transfer_native_back {
+        setup_bridge::<T>()?;
+        fund::<T>(&<bridge::Module<T>>::account_id());
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let amount = <bridge::Module<T>>::to_foreign_amount(T::NativeTokenId::get(), native_amount::<T>().saturated_into())?;
+    }: _(bridge_origin::<T>(), recipient.clone(), amount, T::NativeTokenId::get())
+    verify {
+        assert_eq!(<T as Trait>::Currency::free_balance(&recipient), native_amount::<T>());
+    }
+
+    // the fee is taken from the rtoken itself
+    transfer_rtoken {
+        setup_bridge::<T>()?;
+        <bridge::Module<T>>::set_fee_currency(RawOrigin::Root.into(), DEST_ID, rtoken_resource(), FeeCurrency::Asset)?;
+        let caller: T::AccountId = whitelisted_caller();
+        let amount: u128 = native_amount::<T>().saturated_into();
+        T::RCurrency::mint(&caller, SYMBOL, amount.saturating_mul(2))?;
+    }: _(RawOrigin::Signed(caller), SYMBOL, amount, vec![1; 20], DEST_ID)
+    verify {
+        assert_eq!(BridgeSwap::<T>::locked_supply(rtoken_resource()), amount);
+    }
+
+    transfer_rtoken_back {
+        setup_bridge::<T>()?;
+        let amount: u128 = native_amount::<T>().saturated_into();
+        T::RCurrency::mint(&<bridge::Module<T>>::account_id(), SYMBOL, amount)?;
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let foreign_amount = <bridge::Module<T>>::to_foreign_amount(rtoken_resource(), amount)?;
+    }: _(bridge_origin::<T>(), recipient.clone(), foreign_amount, rtoken_resource())
+    verify {
+        assert_eq!(T::RCurrency::free_balance(&recipient, SYMBOL), amount);
+    }
+
+    set_asset_mode {
+    }: _(RawOrigin::Root, rtoken_resource(), AssetMode::BurnMint)
+    verify {
+        assert_eq!(BridgeSwap::<T>::asset_modes(rtoken_resource()), AssetMode::BurnMint);
+    }
+
+    set_transfer_limit {
+    }: _(RawOrigin::Root, rtoken_resource(), 1, 10, 100)
+    verify {
+        assert!(BridgeSwap::<T>::transfer_limits(rtoken_resource()).is_some());
+    }
+
+    remove_transfer_limit {
+        BridgeSwap::<T>::set_transfer_limit(RawOrigin::Root.into(), rtoken_resource(), 1, 10, 100)?;
+    }: _(RawOrigin::Root, rtoken_resource())
+    verify {
+        assert!(BridgeSwap::<T>::transfer_limits(rtoken_resource()).is_none());
+    }
+
+    release_queued_transfer {
+        setup_bridge::<T>()?;
+        fund::<T>(&<bridge::Module<T>>::account_id());
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        BridgeSwap::<T>::queue_transfer(recipient.clone(), T::NativeTokenId::get(), native_amount::<T>().saturated_into(), None);
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), 0)
+    verify {
+        assert_eq!(<T as Trait>::Currency::free_balance(&recipient), native_amount::<T>());
+    }
+
+    force_release_queued_transfer {
+        setup_bridge::<T>()?;
+        fund::<T>(&<bridge::Module<T>>::account_id());
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        BridgeSwap::<T>::queue_transfer(recipient.clone(), T::NativeTokenId::get(), native_amount::<T>().saturated_into(), None);
+    }: _(RawOrigin::Root, 0)
+    verify {
+        assert_eq!(<T as Trait>::Currency::free_balance(&recipient), native_amount::<T>());
+    }
+
+    cancel_queued_transfer {
+        setup_bridge::<T>()?;
+        fund::<T>(&<bridge::Module<T>>::account_id());
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let amount: u128 = native_amount::<T>().saturated_into();
+        bridge::ExecutingNonce::put((DEST_ID, 1));
+        BridgeSwap::<T>::queue_transfer(recipient, T::NativeTokenId::get(), amount, None);
+        bridge::ExecutingNonce::kill();
+        let deposit = bridge::SourceDeposit {
+            resource_id: T::NativeTokenId::get(),
+            depositor: vec![1; 20],
+            amount: <bridge::Module<T>>::to_foreign_amount(T::NativeTokenId::get(), amount)?,
+        };
+        bridge::SourceDeposits::insert(DEST_ID, 1, deposit);
+    }: _(RawOrigin::Root, 0)
+    verify {
+        assert!(BridgeSwap::<T>::queued_transfers(0).is_none());
+    }
+
+    transfer_generic {
+        setup_bridge::<T>()?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), generic_resource(), vec![0; 256], DEST_ID)
+    verify {
+        assert_eq!(<bridge::Module<T>>::chains(DEST_ID), Some(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_transfer_native::<Test>());
+            assert_ok!(test_benchmark_transfer_native_back::<Test>());
+            assert_ok!(test_benchmark_transfer_rtoken::<Test>());
+            assert_ok!(test_benchmark_transfer_rtoken_back::<Test>());
+            assert_ok!(test_benchmark_set_asset_mode::<Test>());
+            assert_ok!(test_benchmark_set_transfer_limit::<Test>());
+            assert_ok!(test_benchmark_remove_transfer_limit::<Test>());
+            assert_ok!(test_benchmark_release_queued_transfer::<Test>());
+            assert_ok!(test_benchmark_force_release_queued_transfer::<Test>());
+            assert_ok!(test_benchmark_cancel_queued_transfer::<Test>());
+            assert_ok!(test_benchmark_transfer_generic::<Test>());
+        });
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Default weights of bridge-swap, conservative estimates until the pallet is benchmarked.
+//! Base weights are estimates of the computation with a fifth added, rounded up. The storage
+//! reads and writes are counted from the code, including the ones of bridge-common the transfers go through.
+
+use frame_support::weights::{Weight, constants::RocksDbWeight as DbWeight};
+
+impl crate::WeightInfo for () {
+	fn transfer_native() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(14, 8))
+	}
+	fn transfer_native_back() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(7, 4))
+	}
+	fn transfer_rtoken() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(17, 10))
+	}
+	fn transfer_rtoken_back() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(9, 5))
+	}
+	fn set_asset_mode() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(2, 1))
+	}
+	fn set_transfer_limit() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(2))
+	}
+	fn remove_transfer_limit() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(1, 2))
+	}
+	fn release_queued_transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(8, 5))
+	}
+	fn force_release_queued_transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(7, 5))
+	}
+	fn cancel_queued_transfer() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(9, 4))
+	}
+	fn transfer_nonfungible() -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(10, 4))
+	}
+	fn transfer_nonfungible_back() -> Weight {
+		(20_000_000 as Weight)
+	}
+	fn transfer_generic() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads_writes(10, 4))
+	}
+	fn execute_generic() -> Weight {
+		(20_000_000 as Weight)
+	}
+}
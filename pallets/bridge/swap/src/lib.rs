@@ -12,29 +12,120 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
-use bridge_common::{self as bridge, ResourceId};
+use codec::{Decode, Encode};
+use bridge_common::{self as bridge, ResourceId, NonFungibleHandler, GenericHandler, FeeCurrency};
 use frame_support::{
-    decl_error, decl_module, dispatch::DispatchResult, ensure,
+    decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult, ensure, transactional,
     traits::{
-        Currency, EnsureOrigin, Get,
+        Currency, EnsureOrigin, Filter, Get, IsSubType,
         ExistenceRequirement::{AllowDeath, KeepAlive},
     },
+    weights::Weight,
 };
 use frame_system::{self as system, ensure_signed};
-use sp_runtime::{traits::{Zero, Saturating}};
+use sp_runtime::{Perbill, RuntimeDebug, traits::{Zero, Saturating}};
 use sp_core::U256;
 use sp_arithmetic::traits::SaturatedConversion;
-use node_primitives::{ChainId, ETH_CHAIN_ID, RSymbol};
+use node_primitives::{ChainId, RSymbol};
 use rtoken_balances::{traits::{Currency as RCurrency}};
 
+pub mod default_weights;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
+/// Limits on the value bridged for a resource
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct TransferLimit {
+    /// Minimum amount of an outbound transfer
+    pub min: u128,
+    /// Maximum amount of a single transfer
+    pub max: u128,
+    /// Maximum amount bridged in each direction over a sliding window of `LimitPeriod` blocks
+    pub cap: u128,
+}
+
+/// Amount bridged for a resource in the last two limit periods, the window the cap applies to
+/// spans the current one and the tail of the previous one.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct PeriodVolume<BlockNumber> {
+    /// Index of the current period
+    pub period: BlockNumber,
+    /// Amount bridged during the current period
+    pub current: u128,
+    /// Amount bridged during the period before it
+    pub previous: u128,
+}
+
+/// Inbound transfer held back because it exceeded the limits of its resource
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct QueuedTransfer<AccountId> {
+    pub recipient: AccountId,
+    pub resource_id: ResourceId,
+    pub amount: u128,
+    /// rtoken to be transferred, native token if `None`
+    pub symbol: Option<RSymbol>,
+    /// Source chain and nonce of the proposal which carried the transfer
+    pub source: Option<(ChainId, bridge::DepositNonce)>,
+}
+
+/// How a resource crosses the bridge
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum AssetMode {
+    /// Outbound transfers lock the asset in the bridge account, inbound ones unlock it.
+    /// Used for assets native to this chain.
+    LockUnlock,
+    /// Outbound transfers burn the asset, inbound ones mint it.
+    /// Used for assets native to the other chain.
+    BurnMint,
+}
+
+impl Default for AssetMode {
+    fn default() -> Self {
+        AssetMode::LockUnlock
+    }
+}
+
+/// Restricts relayer proposals to the inbound calls of this pallet, for `bridge::Trait::ProposalFilter`
+pub struct ProposalFilter<T>(sp_std::marker::PhantomData<T>);
+impl<T: Trait, C: IsSubType<Call<T>>> Filter<C> for ProposalFilter<T> {
+    fn filter(call: &C) -> bool {
+        matches!(
+            call.is_sub_type(),
+            Some(Call::transfer_native_back(..)) |
+            Some(Call::transfer_rtoken_back(..)) |
+            Some(Call::transfer_nonfungible_back(..)) |
+            Some(Call::execute_generic(..))
+        )
+    }
+}
+
+/// Weights of the handlers' own work are not included for the non-fungible and generic calls.
+pub trait WeightInfo {
+    fn transfer_native() -> Weight;
+    fn transfer_native_back() -> Weight;
+    fn transfer_rtoken() -> Weight;
+    fn transfer_rtoken_back() -> Weight;
+    fn set_asset_mode() -> Weight;
+    fn set_transfer_limit() -> Weight;
+    fn remove_transfer_limit() -> Weight;
+    fn release_queued_transfer() -> Weight;
+    fn force_release_queued_transfer() -> Weight;
+    fn cancel_queued_transfer() -> Weight;
+    fn transfer_nonfungible() -> Weight;
+    fn transfer_nonfungible_back() -> Weight;
+    fn transfer_generic() -> Weight;
+    fn execute_generic() -> Weight;
+}
+
 pub trait Trait: system::Trait + bridge::Trait {
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
     /// The currency mechanism.
     type Currency: Currency<Self::AccountId>;
     /// Currency mechanism of rtoken
@@ -45,18 +136,102 @@ pub trait Trait: system::Trait + bridge::Trait {
 
     // Ids can be defined by the runtime and passed in, perhaps from blake2b_128 hashes.
     type NativeTokenId: Get<ResourceId>;
+
+    /// Handler of the non-fungible tokens sent over the bridge
+    type NonFungibleHandler: NonFungibleHandler<Self::AccountId>;
+    /// Handler of the generic messages received over the bridge
+    type GenericHandler: GenericHandler;
+
+    /// Length of the window in which `TransferLimit::cap` applies
+    type LimitPeriod: Get<Self::BlockNumber>;
+
+    /// Weight information for the extrinsics of this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_event! {
+    pub enum Event<T> where
+        AccountId = <T as system::Trait>::AccountId,
+        BlockNumber = <T as system::Trait>::BlockNumber,
+    {
+        /// Limits of a resource changed (resource_id, min, max, cap)
+        TransferLimitSet(ResourceId, u128, u128, u128),
+        /// Removal of the limits of a resource announced (resource_id, applying_at)
+        TransferLimitRemovalAnnounced(ResourceId, BlockNumber),
+        /// Limits of a resource removed
+        TransferLimitRemoved(ResourceId),
+        /// Inbound transfer exceeded the limits and got queued (id, recipient, resource_id, amount)
+        TransferQueued(u64, AccountId, ResourceId, u128),
+        /// Queued transfer paid out
+        QueuedTransferReleased(u64),
+        /// Queued transfer dropped by the admin and sent back to its depositor
+        QueuedTransferCancelled(u64),
+        /// Mode of a resource changed
+        AssetModeSet(ResourceId, AssetMode),
+    }
 }
 
 decl_error! {
     pub enum Error for Module<T: Trait> {
         ServicePaused,
         InvalidChainId,
-        InvalidEthereumAddress,
         InvalidChainFee,
         InvalidFeesRecipientAccount,
         InsufficientRbalance,
         RsymbolNotMapped,
         ResourceNotMapped,
+        /// Amount is below the minimum of the resource
+        AmountTooSmall,
+        /// Amount is above the maximum of the resource
+        AmountTooLarge,
+        /// Amount would exceed the cap of the resource over the last `LimitPeriod` blocks
+        CapExceeded,
+        /// Minimum is larger than maximum or maximum larger than cap
+        InvalidTransferLimit,
+        /// No queued transfer with this id
+        QueuedTransferNotFound,
+        /// Queued transfer didn't come with a proposal, there is no depositor to send it back to
+        QueuedTransferSourceUnknown,
+        /// The native token can only be locked and unlocked
+        NativeTokenMustLock,
+        /// Some supply of the resource is locked or minted, its mode can't change
+        ResourceInUse,
+        /// Resource isn't the one of the native token
+        InvalidResourceId,
+    }
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as BridgeSwap {
+        /// Limits on the value bridged for each resource, unlimited if absent
+        pub TransferLimits get(fn transfer_limits): map hasher(blake2_128_concat) ResourceId => Option<TransferLimit>;
+
+        /// Block at which the announced removal of the limits of each resource applies
+        pub LimitRemovals get(fn limit_removals): map hasher(blake2_128_concat) ResourceId => Option<T::BlockNumber>;
+
+        /// Resources whose limits are removed at each block
+        pub LimitRemovalQueue get(fn limit_removal_queue): map hasher(twox_64_concat) T::BlockNumber => Vec<ResourceId>;
+
+        /// Amount sent out during the last two periods, for each resource
+        pub OutboundVolume get(fn outbound_volume): map hasher(blake2_128_concat) ResourceId => PeriodVolume<T::BlockNumber>;
+
+        /// Amount received during the last two periods, for each resource
+        pub InboundVolume get(fn inbound_volume): map hasher(blake2_128_concat) ResourceId => PeriodVolume<T::BlockNumber>;
+
+        /// Inbound transfers waiting for room under the cap
+        pub QueuedTransfers get(fn queued_transfers): map hasher(twox_64_concat) u64 => Option<QueuedTransfer<T::AccountId>>;
+
+        /// Id of the next queued transfer
+        pub NextQueuedId get(fn next_queued_id): u64;
+
+        /// Whether each resource is locked or burned when leaving the chain
+        pub AssetModes get(fn asset_modes): map hasher(blake2_128_concat) ResourceId => AssetMode;
+
+        /// Amount of each lock/unlock resource held by the bridge account
+        pub LockedSupply get(fn locked_supply): map hasher(blake2_128_concat) ResourceId => u128;
+
+        /// Amount of each burn/mint resource minted by the bridge and not burned yet
+        pub MintedSupply get(fn minted_supply): map hasher(blake2_128_concat) ResourceId => u128;
     }
 }
 
@@ -65,103 +240,433 @@ decl_module! {
         type Error = Error<T>;
 
         const NativeTokenId: ResourceId = T::NativeTokenId::get();
+        const LimitPeriod: T::BlockNumber = T::LimitPeriod::get();
+
+        fn deposit_event() = default;
+
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::apply_due_limit_removals(now)
+        }
 
         /// Transfers some amount of the native token to some recipient on a (whitelisted) destination chain.
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::transfer_native()]
         pub fn transfer_native(origin, amount: BalanceOf<T>, recipient: Vec<u8>, dest_id: ChainId) -> DispatchResult {
             let source = ensure_signed(origin)?;
 
             ensure!(!<bridge::Module<T>>::check_is_paused(), Error::<T>::ServicePaused);
 
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidChainId);
+            let resource_id = T::NativeTokenId::get();
+            <bridge::Module<T>>::ensure_not_paused(dest_id, resource_id)?;
 
-            if dest_id == ETH_CHAIN_ID {
-                Self::check_eth_recipient(recipient.clone())?;
-            }
+            <bridge::Module<T>>::ensure_valid_recipient(dest_id, &recipient)?;
 
-            let chain_fees = <bridge::Module<T>>::get_chain_fees(dest_id)
+            // the transferred asset is AOW whatever currency the fees are set to,
+            // the rate still only applies with `FeeCurrency::Asset`
+            let (chain_fees, _) = <bridge::Module<T>>::quote_fee(dest_id, resource_id, amount.saturated_into())
                 .ok_or_else(|| Error::<T>::InvalidChainFee)?;
             let fees: BalanceOf<T> = chain_fees.saturated_into();
 
             let fees_recipient_account = <bridge::Module<T>>::get_fees_recipient_account()
                 .ok_or_else(|| Error::<T>::InvalidFeesRecipientAccount)?;
 
+            Self::check_outbound(resource_id, amount.saturated_into())?;
+            let foreign_amount = <bridge::Module<T>>::to_foreign_amount(resource_id, amount.saturated_into())?;
+
             let total_amount = amount.saturating_add(fees);
 
             let bridge_id = <bridge::Module<T>>::account_id();
-            T::Currency::transfer(&source, &bridge_id, total_amount.into(), AllowDeath)?;
+            <T as Trait>::Currency::transfer(&source, &bridge_id, total_amount.into(), AllowDeath)?;
 
             if fees > Zero::zero() {
-                T::Currency::transfer(&bridge_id, &fees_recipient_account, fees.into(), KeepAlive)?;
+                <T as Trait>::Currency::transfer(&bridge_id, &fees_recipient_account, fees.into(), KeepAlive)?;
             }
 
-            let resource_id = T::NativeTokenId::get();
-            <bridge::Module<T>>::transfer_fungible(source, dest_id, resource_id, recipient, U256::from(amount.saturated_into()))
+            <LockedSupply>::mutate(resource_id, |s| *s = s.saturating_add(amount.saturated_into()));
+            Self::note_outbound(resource_id, amount.saturated_into());
+            <bridge::Module<T>>::transfer_fungible(source, dest_id, resource_id, recipient, foreign_amount)
         }
 
-        /// Allows the bridge to swap native token back
-        #[weight = 195_000_000]
-        pub fn transfer_native_back(origin, recipient: T::AccountId, amount: BalanceOf<T>, _resource_id: ResourceId) -> DispatchResult {
-            let bridge_id = T::BridgeOrigin::ensure_origin(origin)?;
-            T::Currency::transfer(&bridge_id, &recipient, amount, KeepAlive)?;
+        /// Allows the bridge to swap native token back, `amount` is in the units of the source chain
+        #[weight = <T as Trait>::WeightInfo::transfer_native_back()]
+        pub fn transfer_native_back(origin, recipient: T::AccountId, amount: U256, resource_id: ResourceId) -> DispatchResult {
+            T::BridgeOrigin::ensure_origin(origin)?;
+            ensure!(resource_id == T::NativeTokenId::get(), Error::<T>::InvalidResourceId);
+            let amount = <bridge::Module<T>>::to_local_amount(resource_id, amount)?;
+            if !Self::inbound_allowed(resource_id, amount) {
+                Self::queue_transfer(recipient, resource_id, amount, None);
+                return Ok(());
+            }
+            Self::pay_out(&recipient, resource_id, None, amount)?;
+            Self::note_inbound(resource_id, amount);
 
             Ok(())
         }
         
         /// Transfers some amount of the native token to some recipient on a (whitelisted) destination chain.
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::transfer_rtoken()]
+        #[transactional]
         pub fn transfer_rtoken(origin, symbol: RSymbol, amount: u128, recipient: Vec<u8>, dest_id: ChainId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             ensure!(!<bridge::Module<T>>::check_is_paused(), Error::<T>::ServicePaused);
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidChainId);
 
-            if dest_id == ETH_CHAIN_ID {
-                Self::check_eth_recipient(recipient.clone())?;
-            }
+            <bridge::Module<T>>::ensure_valid_recipient(dest_id, &recipient)?;
 
-            let chain_fees = <bridge::Module<T>>::get_chain_fees(dest_id)
+            let op_resource = <bridge::Module<T>>::rsymbol_resource(&symbol);
+            ensure!(op_resource.is_some(), Error::<T>::RsymbolNotMapped);
+            let resource = op_resource.unwrap();
+            <bridge::Module<T>>::ensure_not_paused(dest_id, resource)?;
+
+            let (fees, fee_currency) = <bridge::Module<T>>::quote_fee(dest_id, resource, amount)
                 .ok_or_else(|| Error::<T>::InvalidChainFee)?;
-            let fees: BalanceOf<T> = chain_fees.saturated_into();
 
             let fees_recipient_account = <bridge::Module<T>>::get_fees_recipient_account()
                 .ok_or_else(|| Error::<T>::InvalidFeesRecipientAccount)?;
 
-            let op_resource = <bridge::Module<T>>::rsymbol_resource(&symbol);
-            ensure!(op_resource.is_some(), Error::<T>::RsymbolNotMapped);
-            let resource = op_resource.unwrap();
-
-            let new_rbalance = T::RCurrency::free_balance(&who, symbol).checked_sub(amount)
+            let withdrawn = match fee_currency {
+                FeeCurrency::Asset => amount.saturating_add(fees),
+                FeeCurrency::Native => amount,
+            };
+            let new_rbalance = T::RCurrency::free_balance(&who, symbol).checked_sub(withdrawn)
             .ok_or(Error::<T>::InsufficientRbalance)?;
-            T::RCurrency::ensure_can_withdraw(&who, symbol, amount, new_rbalance)?;
+            T::RCurrency::ensure_can_withdraw(&who, symbol, withdrawn, new_rbalance)?;
+            Self::check_outbound(resource, amount)?;
+            let foreign_amount = <bridge::Module<T>>::to_foreign_amount(resource, amount)?;
 
             if fees > Zero::zero() {
-                T::Currency::transfer(&who, &fees_recipient_account, fees.into(), KeepAlive)?;
+                match fee_currency {
+                    FeeCurrency::Asset => T::RCurrency::transfer(&who, &fees_recipient_account, symbol, fees)?,
+                    FeeCurrency::Native => <T as Trait>::Currency::transfer(&who, &fees_recipient_account, fees.saturated_into(), KeepAlive)?,
+                }
             }
-            let bridge_id = <bridge::Module<T>>::account_id();
-            T::RCurrency::transfer(&who, &bridge_id, symbol, amount)?;
+            if Self::asset_modes(resource) == AssetMode::BurnMint {
+                T::RCurrency::burn(&who, symbol, amount)?;
+                <MintedSupply>::mutate(resource, |s| *s = s.saturating_sub(amount));
+            } else {
+                let bridge_id = <bridge::Module<T>>::account_id();
+                T::RCurrency::transfer(&who, &bridge_id, symbol, amount)?;
+                <LockedSupply>::mutate(resource, |s| *s = s.saturating_add(amount));
+            }
+            Self::note_outbound(resource, amount);
 
-            <bridge::Module<T>>::transfer_fungible(who, dest_id, resource, recipient, U256::from(amount))
+            <bridge::Module<T>>::transfer_fungible(who, dest_id, resource, recipient, foreign_amount)
         }
 
-        /// Allows the bridge to swap rtoken back
-        #[weight = 195_000_000]
-        pub fn transfer_rtoken_back(origin, recipient: T::AccountId, amount: u128, resource_id: ResourceId) -> DispatchResult {
-            let bridge_id = T::BridgeOrigin::ensure_origin(origin)?;
+        /// Allows the bridge to swap rtoken back, `amount` is in the units of the source chain
+        #[weight = <T as Trait>::WeightInfo::transfer_rtoken_back()]
+        pub fn transfer_rtoken_back(origin, recipient: T::AccountId, amount: U256, resource_id: ResourceId) -> DispatchResult {
+            T::BridgeOrigin::ensure_origin(origin)?;
             let op_sym = <bridge::Module<T>>::resource_rsymbol(&resource_id);
             ensure!(op_sym.is_some(), Error::<T>::ResourceNotMapped);
             let sym = op_sym.unwrap();
-            T::RCurrency::transfer(&bridge_id, &recipient, sym, amount)?;
+            let amount = <bridge::Module<T>>::to_local_amount(resource_id, amount)?;
+            if !Self::inbound_allowed(resource_id, amount) {
+                Self::queue_transfer(recipient, resource_id, amount, Some(sym));
+                return Ok(());
+            }
+            Self::pay_out(&recipient, resource_id, Some(sym), amount)?;
+            Self::note_inbound(resource_id, amount);
             Ok(())
         }
+
+        /// Sets whether a resource is locked or burned when leaving the chain.
+        ///
+        /// The mode can only change while nothing of the resource is locked or minted.
+        #[weight = <T as Trait>::WeightInfo::set_asset_mode()]
+        pub fn set_asset_mode(origin, resource_id: ResourceId, mode: AssetMode) -> DispatchResult {
+            <bridge::Module<T>>::ensure_admin(origin)?;
+            ensure!(resource_id != T::NativeTokenId::get(), Error::<T>::NativeTokenMustLock);
+            ensure!(
+                Self::locked_supply(resource_id).is_zero() && Self::minted_supply(resource_id).is_zero(),
+                Error::<T>::ResourceInUse
+            );
+            <AssetModes>::insert(resource_id, mode);
+
+            Self::deposit_event(RawEvent::AssetModeSet(resource_id, mode));
+            Ok(())
+        }
+
+        /// Sets the limits on the value bridged for a resource, an announced removal of them is dropped.
+        #[weight = <T as Trait>::WeightInfo::set_transfer_limit()]
+        pub fn set_transfer_limit(origin, resource_id: ResourceId, min: u128, max: u128, cap: u128) -> DispatchResult {
+            <bridge::Module<T>>::ensure_admin(origin)?;
+            ensure!(min <= max && max <= cap, Error::<T>::InvalidTransferLimit);
+            <TransferLimits>::insert(resource_id, TransferLimit { min, max, cap });
+            <LimitRemovals<T>>::remove(resource_id);
+
+            Self::deposit_event(RawEvent::TransferLimitSet(resource_id, min, max, cap));
+            Ok(())
+        }
+
+        /// Removes the limits of a resource, once the `AdminDelay` of the bridge has passed.
+        #[weight = <T as Trait>::WeightInfo::remove_transfer_limit()]
+        pub fn remove_transfer_limit(origin, resource_id: ResourceId) -> DispatchResult {
+            <bridge::Module<T>>::ensure_admin(origin)?;
+            let delay = <T as bridge::Trait>::AdminDelay::get();
+            if delay.is_zero() {
+                <TransferLimits>::remove(resource_id);
+                Self::deposit_event(RawEvent::TransferLimitRemoved(resource_id));
+                return Ok(());
+            }
+
+            let at = system::Module::<T>::block_number() + delay;
+            <LimitRemovals<T>>::insert(resource_id, at);
+            <LimitRemovalQueue<T>>::append(at, resource_id);
+
+            Self::deposit_event(RawEvent::TransferLimitRemovalAnnounced(resource_id, at));
+            Ok(())
+        }
+
+        /// Pays out a queued transfer once there is room for it under the cap of the current period,
+        /// unless the route it came in through is paused.
+        #[weight = <T as Trait>::WeightInfo::release_queued_transfer()]
+        pub fn release_queued_transfer(origin, id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            let queued = Self::queued_transfers(id).ok_or(Error::<T>::QueuedTransferNotFound)?;
+            Self::ensure_route_open(&queued)?;
+            ensure!(Self::inbound_fits_cap(queued.resource_id, queued.amount), Error::<T>::CapExceeded);
+
+            Self::release(id, queued)
+        }
+
+        /// Pays out a queued transfer whatever the cap, for the ones above it.
+        #[weight = <T as Trait>::WeightInfo::force_release_queued_transfer()]
+        pub fn force_release_queued_transfer(origin, id: u64) -> DispatchResult {
+            <bridge::Module<T>>::ensure_admin(origin)?;
+            let queued = Self::queued_transfers(id).ok_or(Error::<T>::QueuedTransferNotFound)?;
+            Self::ensure_route_open(&queued)?;
+
+            Self::release(id, queued)
+        }
+
+        /// Drops a queued transfer and sends it back to the depositor on the source chain,
+        /// once the relayers attested the source-side deposit.
+        #[weight = <T as Trait>::WeightInfo::cancel_queued_transfer()]
+        pub fn cancel_queued_transfer(origin, id: u64) -> DispatchResult {
+            <bridge::Module<T>>::ensure_admin(origin)?;
+            let queued = Self::queued_transfers(id).ok_or(Error::<T>::QueuedTransferNotFound)?;
+            let (src_id, nonce) = queued.source.ok_or(Error::<T>::QueuedTransferSourceUnknown)?;
+
+            // the funds never left the bridge account, nor were they minted
+            <bridge::Module<T>>::refund_source_deposit(src_id, nonce, queued.resource_id, queued.amount)?;
+            <QueuedTransfers<T>>::remove(id);
+
+            Self::deposit_event(RawEvent::QueuedTransferCancelled(id));
+            Ok(())
+        }
+
+        /// Transfers a non-fungible token to some recipient on a (whitelisted) destination chain.
+        #[weight = <T as Trait>::WeightInfo::transfer_nonfungible()]
+        #[transactional]
+        pub fn transfer_nonfungible(origin, resource_id: ResourceId, token_id: U256, recipient: Vec<u8>, dest_id: ChainId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!<bridge::Module<T>>::check_is_paused(), Error::<T>::ServicePaused);
+            ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidChainId);
+            <bridge::Module<T>>::ensure_not_paused(dest_id, resource_id)?;
+
+            <bridge::Module<T>>::ensure_valid_recipient(dest_id, &recipient)?;
+
+            Self::pay_chain_fees(&who, dest_id, resource_id)?;
+            let metadata = T::NonFungibleHandler::withdraw(&who, resource_id, token_id)?;
+
+            <bridge::Module<T>>::transfer_nonfungible(who, dest_id, resource_id, token_id, recipient, metadata)
+        }
+
+        /// Allows the bridge to hand a non-fungible token over to its recipient
+        #[weight = <T as Trait>::WeightInfo::transfer_nonfungible_back()]
+        pub fn transfer_nonfungible_back(origin, recipient: T::AccountId, token_id: U256, metadata: Vec<u8>, resource_id: ResourceId) -> DispatchResult {
+            T::BridgeOrigin::ensure_origin(origin)?;
+            T::NonFungibleHandler::deposit(&recipient, resource_id, token_id, metadata)
+        }
+
+        /// Sends arbitrary data to the contract behind `resource_id` on a (whitelisted) destination chain.
+        #[weight = <T as Trait>::WeightInfo::transfer_generic()]
+        pub fn transfer_generic(origin, resource_id: ResourceId, data: Vec<u8>, dest_id: ChainId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!<bridge::Module<T>>::check_is_paused(), Error::<T>::ServicePaused);
+            ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidChainId);
+            <bridge::Module<T>>::ensure_not_paused(dest_id, resource_id)?;
+
+            Self::pay_chain_fees(&who, dest_id, resource_id)?;
+
+            <bridge::Module<T>>::transfer_generic(who, dest_id, resource_id, data)
+        }
+
+        /// Allows the bridge to execute a generic message received from another chain
+        #[weight = <T as Trait>::WeightInfo::execute_generic()]
+        pub fn execute_generic(origin, data: Vec<u8>, resource_id: ResourceId) -> DispatchResult {
+            T::BridgeOrigin::ensure_origin(origin)?;
+            T::GenericHandler::handle(resource_id, data)
+        }
     }
 }
 
 
 impl<T: Trait> Module<T> {
-    pub fn check_eth_recipient(recipient: Vec<u8>) -> DispatchResult {
-        ensure!(recipient.len() == 20, Error::<T>::InvalidEthereumAddress);
+    /// Removes the limits whose announced removal applies at `now`, unless they were set again since
+    fn apply_due_limit_removals(now: T::BlockNumber) -> Weight {
+        let due = <LimitRemovalQueue<T>>::take(now);
+        for resource_id in due.iter() {
+            if Self::limit_removals(resource_id) == Some(now) {
+                <LimitRemovals<T>>::remove(resource_id);
+                <TransferLimits>::remove(resource_id);
+                Self::deposit_event(RawEvent::TransferLimitRemoved(*resource_id));
+            }
+        }
+
+        let removed = due.len() as Weight;
+        T::DbWeight::get().reads_writes(1, 1)
+            .saturating_add(removed.saturating_mul(T::DbWeight::get().reads_writes(1, 2)))
+    }
+
+    /// Index of the limit period `now` falls in
+    fn current_period() -> T::BlockNumber {
+        let period = T::LimitPeriod::get();
+        if period.is_zero() {
+            return Zero::zero();
+        }
+        system::Module::<T>::block_number() / period
+    }
+
+    /// Recorded volume moved forward to the current period
+    fn rolled(recorded: PeriodVolume<T::BlockNumber>) -> PeriodVolume<T::BlockNumber> {
+        let period = Self::current_period();
+        if recorded.period == period {
+            recorded
+        } else if recorded.period.saturating_add(1u32.into()) == period {
+            PeriodVolume { period, current: 0, previous: recorded.current }
+        } else {
+            PeriodVolume { period, ..Default::default() }
+        }
+    }
 
+    /// Amount bridged over the last `LimitPeriod` blocks: all of the current period and the share
+    /// of the previous one still inside the window, assuming it was spread evenly over its blocks.
+    fn volume_in_window(recorded: PeriodVolume<T::BlockNumber>) -> u128 {
+        let volume = Self::rolled(recorded);
+        let period: u64 = T::LimitPeriod::get().saturated_into();
+        if period == 0 {
+            return volume.current;
+        }
+        let now: u64 = system::Module::<T>::block_number().saturated_into();
+        let remaining = period - now % period;
+        let previous = Perbill::from_rational_approximation(remaining, period).mul_ceil(volume.previous);
+        volume.current.saturating_add(previous)
+    }
+
+    /// Adds `amount` to the current period of a recorded volume
+    fn noted(recorded: PeriodVolume<T::BlockNumber>, amount: u128) -> PeriodVolume<T::BlockNumber> {
+        let mut volume = Self::rolled(recorded);
+        volume.current = volume.current.saturating_add(amount);
+        volume
+    }
+
+    /// Checks an outbound transfer against the limits of its resource
+    fn check_outbound(resource_id: ResourceId, amount: u128) -> DispatchResult {
+        if let Some(limit) = Self::transfer_limits(resource_id) {
+            ensure!(amount >= limit.min, Error::<T>::AmountTooSmall);
+            ensure!(amount <= limit.max, Error::<T>::AmountTooLarge);
+            let volume = Self::volume_in_window(Self::outbound_volume(resource_id)).saturating_add(amount);
+            ensure!(volume <= limit.cap, Error::<T>::CapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Adds an outbound transfer to the volume of the current period
+    fn note_outbound(resource_id: ResourceId, amount: u128) {
+        if <TransferLimits>::contains_key(resource_id) {
+            <OutboundVolume<T>>::insert(resource_id, Self::noted(Self::outbound_volume(resource_id), amount));
+        }
+    }
+
+    /// True if an inbound transfer of `amount` still fits under the cap over the last `LimitPeriod` blocks
+    fn inbound_fits_cap(resource_id: ResourceId, amount: u128) -> bool {
+        match Self::transfer_limits(resource_id) {
+            Some(limit) => Self::volume_in_window(Self::inbound_volume(resource_id)).saturating_add(amount) <= limit.cap,
+            None => true,
+        }
+    }
+
+    /// True if an inbound transfer may be paid out right away
+    fn inbound_allowed(resource_id: ResourceId, amount: u128) -> bool {
+        let below_max = Self::transfer_limits(resource_id).map_or(true, |limit| amount <= limit.max);
+        below_max && Self::inbound_fits_cap(resource_id, amount)
+    }
+
+    /// Adds an inbound transfer to the volume of the current period
+    fn note_inbound(resource_id: ResourceId, amount: u128) {
+        if <TransferLimits>::contains_key(resource_id) {
+            <InboundVolume<T>>::insert(resource_id, Self::noted(Self::inbound_volume(resource_id), amount));
+        }
+    }
+
+    /// Hands an inbound transfer over to its recipient, unlocking or minting it depending on the mode of the resource
+    fn pay_out(recipient: &T::AccountId, resource_id: ResourceId, symbol: Option<RSymbol>, amount: u128) -> DispatchResult {
+        let bridge_id = <bridge::Module<T>>::account_id();
+        match symbol {
+            Some(sym) if Self::asset_modes(resource_id) == AssetMode::BurnMint => {
+                T::RCurrency::mint(recipient, sym, amount)?;
+                <MintedSupply>::mutate(resource_id, |s| *s = s.saturating_add(amount));
+            },
+            Some(sym) => {
+                T::RCurrency::transfer(&bridge_id, recipient, sym, amount)?;
+                <LockedSupply>::mutate(resource_id, |s| *s = s.saturating_sub(amount));
+            },
+            None => {
+                <T as Trait>::Currency::transfer(&bridge_id, recipient, amount.saturated_into(), KeepAlive)?;
+                <LockedSupply>::mutate(resource_id, |s| *s = s.saturating_sub(amount));
+            },
+        }
+        Ok(())
+    }
+
+    /// Holds back an inbound transfer until it's released
+    fn queue_transfer(recipient: T::AccountId, resource_id: ResourceId, amount: u128, symbol: Option<RSymbol>) {
+        let id = Self::next_queued_id();
+        <NextQueuedId>::put(id + 1);
+        let source = <bridge::Module<T>>::executing_nonce();
+        <QueuedTransfers<T>>::insert(id, QueuedTransfer { recipient: recipient.clone(), resource_id, amount, symbol, source });
+        Self::deposit_event(RawEvent::TransferQueued(id, recipient, resource_id, amount));
+    }
+
+    /// Pays out a queued transfer and forgets it
+    fn release(id: u64, queued: QueuedTransfer<T::AccountId>) -> DispatchResult {
+        Self::pay_out(&queued.recipient, queued.resource_id, queued.symbol, queued.amount)?;
+        Self::note_inbound(queued.resource_id, queued.amount);
+        <QueuedTransfers<T>>::remove(id);
+
+        Self::deposit_event(RawEvent::QueuedTransferReleased(id));
+        Ok(())
+    }
+
+    /// Checks neither the source chain nor the resource of a queued transfer is paused
+    fn ensure_route_open(queued: &QueuedTransfer<T::AccountId>) -> DispatchResult {
+        match queued.source {
+            Some((src_id, _)) => <bridge::Module<T>>::ensure_not_paused(src_id, queued.resource_id),
+            None => {
+                ensure!(!<bridge::Module<T>>::paused_resources(queued.resource_id), bridge::Error::<T>::ResourcePaused);
+                Ok(())
+            },
+        }
+    }
+
+    /// Charges the flat fee of `resource_id` towards `dest_id` to `who`, paid to the fees recipient account.
+    /// There is no asset to take it from, so it's always paid in AOW.
+    fn pay_chain_fees(who: &T::AccountId, dest_id: ChainId, resource_id: ResourceId) -> DispatchResult {
+        let (chain_fees, _) = <bridge::Module<T>>::quote_fee(dest_id, resource_id, Zero::zero())
+            .ok_or_else(|| Error::<T>::InvalidChainFee)?;
+        let fees: BalanceOf<T> = chain_fees.saturated_into();
+
+        let fees_recipient_account = <bridge::Module<T>>::get_fees_recipient_account()
+            .ok_or_else(|| Error::<T>::InvalidFeesRecipientAccount)?;
+
+        if fees > Zero::zero() {
+            <T as Trait>::Currency::transfer(who, &fees_recipient_account, fees, KeepAlive)?;
+        }
         Ok(())
     }
 }
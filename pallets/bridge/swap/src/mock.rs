@@ -11,11 +11,11 @@
 
 use sp_std::{cell::RefCell};
 use sp_io::hashing::blake2_128;
-use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
-use sp_core::H256;
+use sp_runtime::{Perbill, DispatchError, DispatchResult, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+use sp_core::{H256, U256};
 use frame_support::{impl_outer_origin, impl_outer_dispatch, parameter_types, traits::{Get}, weights::Weight};
 use frame_system::{EnsureRoot};
-use node_primitives::{ChainId, BlockNumber};
+use node_primitives::{ChainId, BlockNumber, ETH_CHAIN_ID};
 use crate::{Module, Trait};
 
 pub(crate) type Balance = u128;
@@ -98,30 +98,116 @@ impl pallet_balances::Trait for Test {
 
 impl rtoken_balances::Trait for Test {
 	type Event = ();
+	type MintOrigin = EnsureRoot<Self::AccountId>;
+	type BurnOrigin = EnsureRoot<Self::AccountId>;
 }
 
 parameter_types! {
 	pub const ChainIdentity: ChainId = 1;
 	pub const ProposalLifetime: BlockNumber = 50;
+	pub const VotesRetention: BlockNumber = 10;
+	pub const MaxSweepPerBlock: u32 = 2;
+	pub const DepositRecordsRetention: u64 = 3;
+	pub const ExecutedNoncesWindow: u64 = 3;
+	pub const MinFeeSubmissions: u32 = 1;
+	pub const MaxProxyAccounts: u32 = 4;
+	pub const MaxFeeSchedules: u32 = 4;
+	pub const MaxRelayers: u32 = 16;
+	pub const RelayerBond: Balance = 0;
+	pub const RelayerReward: Balance = 0;
+	pub const RelayerSlash: Balance = 0;
+}
+
+thread_local! {
+	static ADMIN_DELAY: RefCell<u64> = RefCell::new(0);
+}
+
+pub struct AdminDelay;
+impl AdminDelay {
+	pub fn set(delay: u64) {
+		ADMIN_DELAY.with(|v| *v.borrow_mut() = delay);
+	}
+}
+impl Get<u64> for AdminDelay {
+	fn get() -> u64 {
+		ADMIN_DELAY.with(|v| *v.borrow())
+	}
 }
 
 impl bridge_common::Trait for Test {
 	type Event = ();
 	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type GuardianOrigin = EnsureRoot<Self::AccountId>;
 	type ChainIdentity = ChainIdentity;
 	type Proposal = Call;
+	type ProposalFilter = crate::ProposalFilter<Test>;
 	type ProposalLifetime = ProposalLifetime;
+	type VotesRetention = VotesRetention;
+	type MaxSweepPerBlock = MaxSweepPerBlock;
+	type DepositRecordsRetention = DepositRecordsRetention;
+	type ExecutedNoncesWindow = ExecutedNoncesWindow;
+	type MinFeeSubmissions = MinFeeSubmissions;
+	type MaxProxyAccounts = MaxProxyAccounts;
+	type MaxFeeSchedules = MaxFeeSchedules;
+	type MaxRelayers = MaxRelayers;
+	type AdminDelay = AdminDelay;
+	type Currency = Balances;
+	type RelayerBond = RelayerBond;
+	type RelayerReward = RelayerReward;
+	type RelayerSlash = RelayerSlash;
+	type Slash = ();
+	type WeightInfo = ();
+}
+
+thread_local! {
+	pub static NFT_OWNERS: RefCell<Vec<(u64, U256)>> = RefCell::new(vec![]);
+	pub static GENERIC_MESSAGES: RefCell<Vec<(bridge_common::ResourceId, Vec<u8>)>> = RefCell::new(vec![]);
+}
+
+pub struct TestNonFungibleHandler;
+impl bridge_common::NonFungibleHandler<u64> for TestNonFungibleHandler {
+	fn withdraw(who: &u64, _: bridge_common::ResourceId, token_id: U256) -> Result<Vec<u8>, DispatchError> {
+		NFT_OWNERS.with(|v| {
+			let mut owners = v.borrow_mut();
+			let pos = owners.iter().position(|o| *o == (*who, token_id))
+				.ok_or(DispatchError::Other("not the owner"))?;
+			owners.remove(pos);
+			Ok(token_id.as_u64().to_le_bytes().to_vec())
+		})
+	}
+
+	fn deposit(to: &u64, _: bridge_common::ResourceId, token_id: U256, _: Vec<u8>) -> DispatchResult {
+		NFT_OWNERS.with(|v| v.borrow_mut().push((*to, token_id)));
+		Ok(())
+	}
+}
+
+pub struct TestGenericHandler;
+impl bridge_common::GenericHandler for TestGenericHandler {
+	fn handle(resource_id: bridge_common::ResourceId, data: Vec<u8>) -> DispatchResult {
+		GENERIC_MESSAGES.with(|v| v.borrow_mut().push((resource_id, data)));
+		Ok(())
+	}
 }
 
 parameter_types! {
 	pub NativeTokenId: bridge_common::ResourceId = bridge_common::derive_resource_id(1, &blake2_128(b"FIS"));
 }
 
+parameter_types! {
+	pub const LimitPeriod: u64 = 10;
+}
+
 impl Trait for Test {
+	type Event = ();
 	type Currency = Balances;
 	type RCurrency = RBalances;
 	type NativeTokenId = NativeTokenId;
 	type BridgeOrigin = bridge_common::EnsureBridge<Test>;
+	type NonFungibleHandler = TestNonFungibleHandler;
+	type GenericHandler = TestGenericHandler;
+	type LimitPeriod = LimitPeriod;
+	type WeightInfo = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -133,6 +219,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 				],
 			}.assimilate_storage(&mut t).unwrap();
 
+	bridge_common::GenesisConfig::<Test> {
+		address_formats: vec![(ETH_CHAIN_ID, bridge_common::AddressFormat::Evm)],
+		..Default::default()
+	}.assimilate_storage(&mut t).unwrap();
+
 	t.into()
 }
 
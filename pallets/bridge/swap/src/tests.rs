@@ -13,9 +13,14 @@
 
 use super::*;
 use super::mock::{*, Call};
-use frame_support::{assert_ok, assert_noop, assert_err};
+use frame_support::{assert_ok, assert_noop, traits::OnInitialize};
 use node_primitives::{ETH_CHAIN_ID, RSymbol};
 use sp_runtime::traits::BadOrigin;
+use sp_core::U256;
+
+fn flat_fee(fee: u128) -> bridge::FeeSchedule {
+	bridge::FeeSchedule { flat: fee, rate: Default::default(), min: 0, max: fee }
+}
 
 #[test]
 fn transfer_native_should_work() {
@@ -40,7 +45,7 @@ fn transfer_native_should_work() {
 		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
 		assert_noop!(
 			BridgeSwap::transfer_native(Origin::signed(42), 10, vec![11, 21], ETH_CHAIN_ID),
-			Error::<Test>::InvalidEthereumAddress,
+			bridge::Error::<Test>::InvalidRecipient,
 		);
 
 		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
@@ -51,7 +56,7 @@ fn transfer_native_should_work() {
 
 		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
 		let chain_fees = 10;
-		assert_ok!(BridgeCommon::set_chain_fees(Origin::signed(40), ETH_CHAIN_ID, chain_fees));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, NativeTokenId::get(), flat_fee(chain_fees)));
 		assert_noop!(
 			BridgeSwap::transfer_native(Origin::signed(42), 10, eth_address.clone(), ETH_CHAIN_ID),
 			Error::<Test>::InvalidFeesRecipientAccount,
@@ -93,10 +98,18 @@ fn transfer_rtoken_should_work() {
 		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
 		assert_noop!(
 			BridgeSwap::transfer_rtoken(Origin::signed(42), RSymbol::RFIS, 100, vec![11, 21], ETH_CHAIN_ID),
-			Error::<Test>::InvalidEthereumAddress,
+			bridge::Error::<Test>::InvalidRecipient,
 		);
 
 		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		assert_noop!(
+			BridgeSwap::transfer_rtoken(Origin::signed(42), RSymbol::RFIS, 100, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::RsymbolNotMapped,
+		);
+
+		let rid: ResourceId = [1; 32];
+		let sym: RSymbol = RSymbol::RFIS;
+		assert_ok!(BridgeCommon::map_resource_and_rsymbol(Origin::root(), rid, sym));
 		assert_noop!(
 			BridgeSwap::transfer_rtoken(Origin::signed(42), RSymbol::RFIS, 100, eth_address.clone(), ETH_CHAIN_ID),
 			Error::<Test>::InvalidChainFee,
@@ -104,7 +117,7 @@ fn transfer_rtoken_should_work() {
 
 		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
 		let chain_fees = 10;
-		assert_ok!(BridgeCommon::set_chain_fees(Origin::signed(40), ETH_CHAIN_ID, chain_fees));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, flat_fee(chain_fees)));
 		assert_noop!(
 			BridgeSwap::transfer_rtoken(Origin::signed(42), RSymbol::RFIS, 100, eth_address.clone(), ETH_CHAIN_ID),
 			Error::<Test>::InvalidFeesRecipientAccount,
@@ -112,14 +125,6 @@ fn transfer_rtoken_should_work() {
 
 		let recipient_account = 2;
 		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), recipient_account));
-		assert_noop!(
-			BridgeSwap::transfer_rtoken(Origin::signed(42), RSymbol::RFIS, 100, eth_address.clone(), ETH_CHAIN_ID),
-			Error::<Test>::RsymbolNotMapped,
-        );
-
-        let rid: ResourceId = [1; 32];
-        let sym: RSymbol = RSymbol::RFIS;
-        assert_ok!(BridgeCommon::map_resource_and_rsymbol(Origin::root(), rid, sym));
 
 		assert_noop!(
 			BridgeSwap::transfer_rtoken(Origin::signed(42), RSymbol::RFIS, 100, eth_address.clone(), ETH_CHAIN_ID),
@@ -145,22 +150,27 @@ fn transfer_native_back_should_work() {
     new_test_ext().execute_with(|| {
         let recipient = RELAYER_A;
         let bridge_id: u64 = BridgeCommon::account_id();
-        let rid: ResourceId = [1; 32];
+        let rid = NativeTokenId::get();
 
 		assert_noop!(
-			BridgeSwap::transfer_native_back(Origin::signed(1), recipient, 100, rid),
+			BridgeSwap::transfer_native_back(Origin::signed(1), recipient, 100.into(), rid),
 			BadOrigin,
 		);
 
 		assert_ok!(Balances::transfer(Origin::signed(1), bridge_id, 100));
 
+		assert_noop!(
+			BridgeSwap::transfer_native_back(Origin::signed(bridge_id), recipient, 100.into(), [1; 32]),
+			Error::<Test>::InvalidResourceId,
+		);
+
         // transfer_native_back
-        assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), recipient, 100, rid));
+        assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), recipient, 100.into(), rid));
     })
 }
 
 fn make_transfer_proposal(to: u64, amount: u64) -> Call {
-    let rid: ResourceId = [1; 32];
+    let rid = NativeTokenId::get();
     Call::BridgeSwap(crate::Call::transfer_native_back(to, amount.into(), rid))
 }
 
@@ -169,16 +179,16 @@ fn transfer_native_back_proposal() {
     new_test_ext().execute_with(|| {
         let prop_id = 1;
         let src_id = 2;
-        let rid: ResourceId = [1; 32];
+        let rid = NativeTokenId::get();
         let resource = b"BridgeSwap.transfer_native_back".to_vec();
 		let proposal = make_transfer_proposal(RELAYER_A, 10);
 		
 		assert_ok!(Balances::transfer(Origin::signed(1), BridgeCommon::account_id(), 100));
 
-        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
         assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), src_id));
         assert_ok!(BridgeCommon::add_resource(Origin::root(), rid, resource));
 
@@ -193,8 +203,10 @@ fn transfer_native_back_proposal() {
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = bridge::ProposalVotes {
             voted: vec![RELAYER_A],
+            votes_against: vec![],
             status: bridge_common::ProposalStatus::Active,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: rid,
         };
         assert_eq!(prop, expected);
 
@@ -209,8 +221,10 @@ fn transfer_native_back_proposal() {
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = bridge::ProposalVotes {
             voted: vec![RELAYER_A, RELAYER_C],
+            votes_against: vec![],
             status: bridge::ProposalStatus::Executed,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: rid,
         };
         assert_eq!(prop, expected);
 
@@ -221,7 +235,7 @@ fn transfer_native_back_proposal() {
 
 fn make_transfer_rtoken_proposal(to: u64, amount: u128) -> Call {
     let rid: ResourceId = [1; 32];
-    Call::BridgeSwap(crate::Call::transfer_rtoken_back(to, amount, rid))
+    Call::BridgeSwap(crate::Call::transfer_rtoken_back(to, amount.into(), rid))
 }
 
 #[test]
@@ -237,10 +251,10 @@ fn transfer_rtoken_back_proposal() {
 		let ac = BridgeCommon::account_id();
 		assert_ok!(RBalances::mint(&ac, sym, 100));
 
-        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
         assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
         assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), src_id));
         assert_ok!(BridgeCommon::add_resource(Origin::root(), rid, resource));
 
@@ -255,36 +269,41 @@ fn transfer_rtoken_back_proposal() {
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = bridge::ProposalVotes {
             voted: vec![RELAYER_A],
+            votes_against: vec![],
             status: bridge_common::ProposalStatus::Active,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: rid,
         };
         assert_eq!(prop, expected);
 
-        // Third relayer votes in favour
-        assert_err!(BridgeCommon::acknowledge_proposal(
-            	Origin::signed(RELAYER_C),
-            	prop_id,
-            	src_id,
-            	rid,
-            	Box::new(proposal.clone())
-			),
-			Error::<Test>::ResourceNotMapped
-		);
-		assert_ok!(BridgeCommon::map_resource_and_rsymbol(Origin::root(), rid, sym));
-
-		// Create proposal (& vote)
+        // Third relayer votes in favour, the call fails
         assert_ok!(BridgeCommon::acknowledge_proposal(
-            Origin::signed(RELAYER_B),
+            Origin::signed(RELAYER_C),
             prop_id,
             src_id,
             rid,
             Box::new(proposal.clone())
         ));
         let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        assert_eq!(prop.status, bridge::ProposalStatus::Failed);
+        assert_eq!(BridgeCommon::failed_proposals(src_id, prop_id), Some(Error::<Test>::ResourceNotMapped.into()));
+		assert_ok!(BridgeCommon::map_resource_and_rsymbol(Origin::root(), rid, sym));
+
+		// Retried once the resource is mapped
+        assert_ok!(BridgeCommon::retry_proposal(
+            Origin::signed(RELAYER_B),
+            src_id,
+            prop_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(BridgeCommon::failed_proposals(src_id, prop_id), None);
+        let prop = BridgeCommon::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = bridge::ProposalVotes {
-            voted: vec![RELAYER_A, RELAYER_C, RELAYER_B],
+            voted: vec![RELAYER_A, RELAYER_C],
+            votes_against: vec![],
             status: bridge::ProposalStatus::Executed,
             expiry: ProposalLifetime::get() as u64,
+            resource_id: rid,
         };
         assert_eq!(prop, expected);
 
@@ -292,3 +311,449 @@ fn transfer_rtoken_back_proposal() {
         assert_eq!(RBalances::free_balance(&ac, sym), 90);
     })
 }
+
+#[test]
+fn transfer_nonfungible_should_work() {
+	new_test_ext().execute_with(|| {
+		let rid: ResourceId = [2; 32];
+		let token_id = U256::from(9);
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+
+		assert_noop!(
+			BridgeSwap::transfer_nonfungible(Origin::signed(1), rid, token_id, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::InvalidChainId,
+		);
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_noop!(
+			BridgeSwap::transfer_nonfungible(Origin::signed(1), rid, token_id, vec![11, 21], ETH_CHAIN_ID),
+			bridge::Error::<Test>::InvalidRecipient,
+		);
+		assert_noop!(
+			BridgeSwap::transfer_nonfungible(Origin::signed(1), rid, token_id, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::InvalidChainFee,
+		);
+
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		let chain_fees = 10;
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, flat_fee(chain_fees)));
+		let recipient_account = 2;
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), recipient_account));
+
+		// fees are given back when the token can't be taken
+		assert_noop!(
+			BridgeSwap::transfer_nonfungible(Origin::signed(1), rid, token_id, eth_address.clone(), ETH_CHAIN_ID),
+			sp_runtime::DispatchError::Other("not the owner"),
+		);
+
+		NFT_OWNERS.with(|v| v.borrow_mut().push((1, token_id)));
+		assert_ok!(BridgeSwap::transfer_nonfungible(Origin::signed(1), rid, token_id, eth_address.clone(), ETH_CHAIN_ID));
+		assert!(NFT_OWNERS.with(|v| v.borrow().is_empty()));
+		assert_eq!(Balances::free_balance(&recipient_account), chain_fees);
+		assert_eq!(BridgeCommon::chains(ETH_CHAIN_ID), Some(1));
+	});
+}
+
+#[test]
+fn transfer_nonfungible_back_proposal() {
+	new_test_ext().execute_with(|| {
+		let prop_id = 1;
+		let src_id = 2;
+		let rid: ResourceId = [2; 32];
+		let token_id = U256::from(9);
+		let resource = b"BridgeSwap.transfer_nonfungible_back".to_vec();
+		let proposal = Call::BridgeSwap(crate::Call::transfer_nonfungible_back(RELAYER_A, token_id, vec![1], rid));
+
+		assert_noop!(
+			BridgeSwap::transfer_nonfungible_back(Origin::signed(1), RELAYER_A, token_id, vec![1], rid),
+			BadOrigin,
+		);
+
+		assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_A));
+		assert_ok!(BridgeCommon::add_relayer(Origin::root(), RELAYER_B));
+		assert_ok!(BridgeCommon::set_threshold(Origin::root(), TEST_THRESHOLD));
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), src_id));
+		assert_ok!(BridgeCommon::add_resource(Origin::root(), rid, resource));
+
+		assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_A), prop_id, src_id, rid, Box::new(proposal.clone())));
+		assert!(NFT_OWNERS.with(|v| v.borrow().is_empty()));
+		assert_ok!(BridgeCommon::acknowledge_proposal(Origin::signed(RELAYER_B), prop_id, src_id, rid, Box::new(proposal.clone())));
+
+		let prop = BridgeCommon::votes(src_id, (prop_id, proposal)).unwrap();
+		assert_eq!(prop.status, bridge::ProposalStatus::Executed);
+		assert_eq!(NFT_OWNERS.with(|v| v.borrow().clone()), vec![(RELAYER_A, token_id)]);
+	});
+}
+
+#[test]
+fn transfer_generic_should_work() {
+	new_test_ext().execute_with(|| {
+		let rid: ResourceId = [3; 32];
+		let data = vec![1, 2, 3];
+
+		assert_noop!(
+			BridgeSwap::transfer_generic(Origin::signed(1), rid, data.clone(), ETH_CHAIN_ID),
+			Error::<Test>::InvalidChainId,
+		);
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, flat_fee(10)));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+
+		assert_ok!(BridgeSwap::transfer_generic(Origin::signed(1), rid, data.clone(), ETH_CHAIN_ID));
+		assert_eq!(Balances::free_balance(&2), 10);
+		assert_eq!(BridgeCommon::chains(ETH_CHAIN_ID), Some(1));
+	});
+}
+
+#[test]
+fn execute_generic_should_work() {
+	new_test_ext().execute_with(|| {
+		let rid: ResourceId = [3; 32];
+		let data = vec![1, 2, 3];
+
+		assert_noop!(
+			BridgeSwap::execute_generic(Origin::signed(1), data.clone(), rid),
+			BadOrigin,
+		);
+		assert_ok!(BridgeSwap::execute_generic(Origin::signed(BridgeCommon::account_id()), data.clone(), rid));
+		assert_eq!(GENERIC_MESSAGES.with(|v| v.borrow().clone()), vec![(rid, data)]);
+	});
+}
+
+#[test]
+fn transfer_limits_bound_outbound_transfers() {
+	new_test_ext().execute_with(|| {
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		let rid = NativeTokenId::get();
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, flat_fee(0)));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+
+		assert_noop!(BridgeSwap::set_transfer_limit(Origin::signed(1), rid, 5, 20, 30), BadOrigin);
+		assert_noop!(
+			BridgeSwap::set_transfer_limit(Origin::root(), rid, 5, 40, 30),
+			Error::<Test>::InvalidTransferLimit,
+		);
+		assert_ok!(BridgeSwap::set_transfer_limit(Origin::root(), rid, 5, 20, 30));
+
+		assert_noop!(
+			BridgeSwap::transfer_native(Origin::signed(1), 4, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::AmountTooSmall,
+		);
+		assert_noop!(
+			BridgeSwap::transfer_native(Origin::signed(1), 21, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::AmountTooLarge,
+		);
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 20, eth_address.clone(), ETH_CHAIN_ID));
+		assert_noop!(
+			BridgeSwap::transfer_native(Origin::signed(1), 11, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::CapExceeded,
+		);
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 10, eth_address.clone(), ETH_CHAIN_ID));
+
+		// the cap applies again once the transfers are out of the window
+		System::set_block_number(2 * LimitPeriod::get());
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 20, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(Balances::free_balance(BridgeCommon::account_id()), 50);
+	});
+}
+
+#[test]
+fn transfer_cap_applies_over_a_sliding_window() {
+	new_test_ext().execute_with(|| {
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		let rid = NativeTokenId::get();
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, flat_fee(0)));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+		assert_ok!(BridgeSwap::set_transfer_limit(Origin::root(), rid, 0, 30, 30));
+
+		// the whole cap spent at the end of a period
+		System::set_block_number(LimitPeriod::get() - 1);
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 30, eth_address.clone(), ETH_CHAIN_ID));
+
+		// still inside the window at the start of the next one
+		System::set_block_number(LimitPeriod::get());
+		assert_noop!(
+			BridgeSwap::transfer_native(Origin::signed(1), 1, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::CapExceeded,
+		);
+
+		// half way through, half of the previous period is left in the window
+		System::set_block_number(LimitPeriod::get() * 3 / 2);
+		assert_noop!(
+			BridgeSwap::transfer_native(Origin::signed(1), 16, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::CapExceeded,
+		);
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 15, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(
+			BridgeSwap::outbound_volume(rid),
+			PeriodVolume { period: 1, current: 15, previous: 30 },
+		);
+
+		// the next period only counts what was sent in this one
+		System::set_block_number(2 * LimitPeriod::get());
+		assert_noop!(
+			BridgeSwap::transfer_native(Origin::signed(1), 16, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::CapExceeded,
+		);
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 15, eth_address.clone(), ETH_CHAIN_ID));
+	});
+}
+
+#[test]
+fn transfer_limits_are_removed_after_the_admin_delay() {
+	new_test_ext().execute_with(|| {
+		let rid = NativeTokenId::get();
+		AdminDelay::set(10);
+		assert_ok!(BridgeSwap::set_transfer_limit(Origin::root(), rid, 0, 20, 30));
+
+		assert_noop!(BridgeSwap::remove_transfer_limit(Origin::signed(1), rid), BadOrigin);
+		assert_ok!(BridgeSwap::remove_transfer_limit(Origin::root(), rid));
+		assert_eq!(BridgeSwap::limit_removals(rid), Some(10));
+		BridgeSwap::on_initialize(9);
+		assert!(BridgeSwap::transfer_limits(rid).is_some());
+		BridgeSwap::on_initialize(10);
+		assert_eq!(BridgeSwap::transfer_limits(rid), None);
+		assert_eq!(BridgeSwap::limit_removals(rid), None);
+
+		// setting the limits again drops the announced removal
+		System::set_block_number(20);
+		assert_ok!(BridgeSwap::set_transfer_limit(Origin::root(), rid, 0, 20, 30));
+		assert_ok!(BridgeSwap::remove_transfer_limit(Origin::root(), rid));
+		assert_ok!(BridgeSwap::set_transfer_limit(Origin::root(), rid, 0, 10, 30));
+		BridgeSwap::on_initialize(30);
+		assert_eq!(BridgeSwap::transfer_limits(rid).map(|l| l.max), Some(10));
+	});
+}
+
+#[test]
+fn inbound_transfers_over_limits_are_queued() {
+	new_test_ext().execute_with(|| {
+		let rid = NativeTokenId::get();
+		let bridge_id = BridgeCommon::account_id();
+		assert_ok!(Balances::transfer(Origin::signed(1), bridge_id, 100));
+		assert_ok!(BridgeSwap::set_transfer_limit(Origin::root(), rid, 0, 20, 30));
+
+		assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), RELAYER_A, 20.into(), rid));
+		assert_eq!(Balances::free_balance(RELAYER_A), 20);
+
+		// over the cap of the period
+		assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), RELAYER_B, 15.into(), rid));
+		assert_eq!(Balances::free_balance(RELAYER_B), 0);
+		assert_eq!(
+			BridgeSwap::queued_transfers(0),
+			Some(QueuedTransfer { recipient: RELAYER_B, resource_id: rid, amount: 15, symbol: None, source: None }),
+		);
+		// over the maximum of a single transfer
+		assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), RELAYER_C, 25.into(), rid));
+		assert_eq!(BridgeSwap::queued_transfers(1).map(|q| q.amount), Some(25));
+
+		assert_noop!(BridgeSwap::release_queued_transfer(Origin::signed(1), 0), Error::<Test>::CapExceeded);
+		assert_noop!(BridgeSwap::release_queued_transfer(Origin::signed(1), 2), Error::<Test>::QueuedTransferNotFound);
+
+		System::set_block_number(2 * LimitPeriod::get());
+		assert_ok!(BridgeCommon::pause_resource(Origin::root(), rid));
+		assert_noop!(BridgeSwap::release_queued_transfer(Origin::signed(1), 0), bridge::Error::<Test>::ResourcePaused);
+		assert_ok!(BridgeCommon::unpause_resource(Origin::root(), rid));
+		assert_ok!(BridgeSwap::release_queued_transfer(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(RELAYER_B), 15);
+		assert_eq!(BridgeSwap::queued_transfers(0), None);
+
+		// never under the cap, only the admin can pay it out
+		assert_noop!(BridgeSwap::release_queued_transfer(Origin::signed(1), 1), Error::<Test>::CapExceeded);
+		assert_noop!(BridgeSwap::force_release_queued_transfer(Origin::signed(1), 1), BadOrigin);
+		assert_noop!(BridgeSwap::cancel_queued_transfer(Origin::root(), 1), Error::<Test>::QueuedTransferSourceUnknown);
+		assert_ok!(BridgeSwap::force_release_queued_transfer(Origin::root(), 1));
+		assert_eq!(Balances::free_balance(RELAYER_C), 25);
+		assert_eq!(BridgeSwap::queued_transfers(1), None);
+	});
+}
+
+#[test]
+fn queued_transfers_keep_their_source() {
+	new_test_ext().execute_with(|| {
+		let rid = NativeTokenId::get();
+		let bridge_id = BridgeCommon::account_id();
+		assert_ok!(Balances::transfer(Origin::signed(1), bridge_id, 100));
+		assert_ok!(BridgeSwap::set_transfer_limit(Origin::root(), rid, 0, 10, 30));
+
+		// as if dispatched from the proposal of nonce 1 from ETH_CHAIN_ID
+		bridge::ExecutingNonce::put((ETH_CHAIN_ID, 1));
+		assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), RELAYER_A, 20.into(), rid));
+		bridge::ExecutingNonce::kill();
+		assert_eq!(BridgeSwap::queued_transfers(0).and_then(|q| q.source), Some((ETH_CHAIN_ID, 1)));
+
+		assert_ok!(BridgeCommon::pause_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_noop!(BridgeSwap::release_queued_transfer(Origin::signed(1), 0), bridge::Error::<Test>::ChainPaused);
+		assert_noop!(BridgeSwap::force_release_queued_transfer(Origin::root(), 0), bridge::Error::<Test>::ChainPaused);
+		assert_ok!(BridgeCommon::unpause_chain(Origin::root(), ETH_CHAIN_ID));
+
+		// cancelled back to the depositor attested on the source chain
+		let depositor = vec![11; 20];
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_noop!(BridgeSwap::cancel_queued_transfer(Origin::signed(1), 0), BadOrigin);
+		assert_noop!(BridgeSwap::cancel_queued_transfer(Origin::root(), 0), bridge::Error::<Test>::SourceDepositUnknown);
+		bridge::SourceDeposits::insert(ETH_CHAIN_ID, 1, bridge::SourceDeposit {
+			resource_id: rid,
+			depositor: depositor.clone(),
+			amount: 20.into(),
+		});
+		assert_ok!(BridgeSwap::cancel_queued_transfer(Origin::root(), 0));
+		assert_eq!(BridgeSwap::queued_transfers(0), None);
+		assert_eq!(BridgeCommon::source_deposits(ETH_CHAIN_ID, 1), None);
+		assert_eq!(Balances::free_balance(RELAYER_A), 0);
+		assert_eq!(
+			BridgeCommon::deposit_records(ETH_CHAIN_ID, 1).map(|r| r.data),
+			Some(bridge::DepositData::Fungible(20.into(), depositor)),
+		);
+	});
+}
+
+#[test]
+fn burn_mint_resources_are_burned_and_minted() {
+	new_test_ext().execute_with(|| {
+		let rid: ResourceId = [1; 32];
+		let sym: RSymbol = RSymbol::RFIS;
+		let bridge_id = BridgeCommon::account_id();
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, flat_fee(10)));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+		assert_ok!(BridgeCommon::map_resource_and_rsymbol(Origin::root(), rid, sym));
+		assert_ok!(Balances::transfer(Origin::signed(1), 42, 100));
+
+		assert_noop!(BridgeSwap::set_asset_mode(Origin::signed(1), rid, AssetMode::BurnMint), BadOrigin);
+		assert_noop!(
+			BridgeSwap::set_asset_mode(Origin::root(), NativeTokenId::get(), AssetMode::BurnMint),
+			Error::<Test>::NativeTokenMustLock,
+		);
+		assert_ok!(BridgeSwap::set_asset_mode(Origin::root(), rid, AssetMode::BurnMint));
+		assert_eq!(BridgeSwap::asset_modes(rid), AssetMode::BurnMint);
+
+		// inbound transfers mint without any funds in the bridge account
+		assert_ok!(BridgeSwap::transfer_rtoken_back(Origin::signed(bridge_id), 42, 100.into(), rid));
+		assert_eq!(RBalances::free_balance(&42, sym), 100);
+		assert_eq!(RBalances::total_issuance(sym), 100);
+		assert_eq!(BridgeSwap::minted_supply(rid), 100);
+
+		assert_noop!(
+			BridgeSwap::set_asset_mode(Origin::root(), rid, AssetMode::LockUnlock),
+			Error::<Test>::ResourceInUse,
+		);
+
+		// outbound transfers burn
+		assert_ok!(BridgeSwap::transfer_rtoken(Origin::signed(42), sym, 60, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(RBalances::free_balance(&42, sym), 40);
+		assert_eq!(RBalances::free_balance(&bridge_id, sym), 0);
+		assert_eq!(RBalances::total_issuance(sym), 40);
+		assert_eq!(BridgeSwap::minted_supply(rid), 40);
+		assert_eq!(BridgeSwap::locked_supply(rid), 0);
+	});
+}
+
+#[test]
+fn lock_unlock_resources_track_locked_supply() {
+	new_test_ext().execute_with(|| {
+		let native = NativeTokenId::get();
+		let bridge_id = BridgeCommon::account_id();
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, native, flat_fee(10)));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 50, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(BridgeSwap::locked_supply(native), 50);
+		assert_eq!(Balances::free_balance(bridge_id), 50);
+
+		assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), RELAYER_A, 30.into(), native));
+		assert_eq!(Balances::free_balance(RELAYER_A), 30);
+		assert_eq!(BridgeSwap::locked_supply(native), 20);
+		assert_eq!(BridgeSwap::minted_supply(native), 0);
+	});
+}
+
+#[test]
+fn amounts_are_scaled_across_the_bridge() {
+	new_test_ext().execute_with(|| {
+		let native = NativeTokenId::get();
+		let bridge_id = BridgeCommon::account_id();
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, native, flat_fee(0)));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+		assert_ok!(BridgeCommon::set_resource_decimals(Origin::root(), native, 1, 3));
+
+		assert_ok!(BridgeSwap::transfer_native(Origin::signed(1), 50, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(
+			BridgeCommon::deposit_records(ETH_CHAIN_ID, 1).map(|r| r.data),
+			Some(bridge::DepositData::Fungible(U256::from(5_000), eth_address.clone())),
+		);
+
+		assert_noop!(
+			BridgeSwap::transfer_native_back(Origin::signed(bridge_id), RELAYER_A, 2_001.into(), native),
+			bridge::Error::<Test>::AmountNotRepresentable,
+		);
+		assert_ok!(BridgeSwap::transfer_native_back(Origin::signed(bridge_id), RELAYER_A, 2_000.into(), native));
+		assert_eq!(Balances::free_balance(RELAYER_A), 20);
+	});
+}
+
+#[test]
+fn rtoken_fees_can_be_paid_in_the_asset() {
+	new_test_ext().execute_with(|| {
+		let rid: ResourceId = [1; 32];
+		let sym: RSymbol = RSymbol::RFIS;
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::map_resource_and_rsymbol(Origin::root(), rid, sym));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+		// 1% of the amount, at least 5
+		let schedule = bridge::FeeSchedule { flat: 0, rate: sp_runtime::Permill::from_percent(1), min: 5, max: 50 };
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, schedule));
+		assert_ok!(BridgeCommon::set_fee_currency(Origin::root(), ETH_CHAIN_ID, rid, FeeCurrency::Asset));
+
+		// no AOW needed, but the fee must come on top of the amount
+		assert_ok!(RBalances::mint(&42, sym, 1_000));
+		assert_noop!(
+			BridgeSwap::transfer_rtoken(Origin::signed(42), sym, 1_000, eth_address.clone(), ETH_CHAIN_ID),
+			Error::<Test>::InsufficientRbalance,
+		);
+		assert_ok!(BridgeSwap::transfer_rtoken(Origin::signed(42), sym, 900, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(RBalances::free_balance(&2, sym), 9);
+		assert_eq!(RBalances::free_balance(&BridgeCommon::account_id(), sym), 900);
+		assert_eq!(RBalances::free_balance(&42, sym), 91);
+
+		assert_ok!(BridgeSwap::transfer_rtoken(Origin::signed(42), sym, 50, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(RBalances::free_balance(&2, sym), 14);
+		assert_eq!(RBalances::free_balance(&42, sym), 36);
+	});
+}
+
+#[test]
+fn rtoken_fees_paid_in_aow_are_flat() {
+	new_test_ext().execute_with(|| {
+		let rid: ResourceId = [1; 32];
+		let sym: RSymbol = RSymbol::RFIS;
+		let eth_address = vec![11, 21, 31, 43, 88, 120, 43, 54, 55, 99, 54, 98, 23, 24, 54, 64, 29, 94, 26, 75];
+		assert_ok!(BridgeCommon::whitelist_chain(Origin::root(), ETH_CHAIN_ID));
+		assert_ok!(BridgeCommon::map_resource_and_rsymbol(Origin::root(), rid, sym));
+		assert_ok!(BridgeCommon::set_proxy_accounts(Origin::root(), 40));
+		assert_ok!(BridgeCommon::set_fees_recipient_account(Origin::root(), 2));
+		let schedule = bridge::FeeSchedule { flat: 3, rate: sp_runtime::Permill::from_percent(1), min: 0, max: 50 };
+		assert_ok!(BridgeCommon::submit_fee_schedule(Origin::signed(40), ETH_CHAIN_ID, rid, schedule));
+
+		// 1% of the rtoken amount would be 10 AOW
+		assert_ok!(RBalances::mint(&1, sym, 1_000));
+		assert_ok!(BridgeSwap::transfer_rtoken(Origin::signed(1), sym, 1_000, eth_address.clone(), ETH_CHAIN_ID));
+		assert_eq!(Balances::free_balance(2), 3);
+		assert_eq!(Balances::free_balance(1), 97);
+		assert_eq!(RBalances::free_balance(&BridgeCommon::account_id(), sym), 1_000);
+	});
+}
@@ -0,0 +1,57 @@
+//! Conviction a vote is cast with.
+
+use codec::{Decode, Encode};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{AtLeast32BitUnsigned, Saturating},
+};
+
+/// Voting weight multiplier, paid for by keeping the voted balance locked after the vote closes.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+pub enum Conviction {
+	/// 1x votes, unlocked when the vote closes.
+	None,
+	/// 2x votes, locked for 1 period after the vote closes.
+	Locked2x,
+	/// 3x votes, locked for 2 periods.
+	Locked3x,
+	/// 4x votes, locked for 4 periods.
+	Locked4x,
+	/// 5x votes, locked for 8 periods.
+	Locked5x,
+	/// 6x votes, locked for 16 periods.
+	Locked6x,
+}
+
+impl Default for Conviction {
+	fn default() -> Self {
+		Conviction::None
+	}
+}
+
+impl Conviction {
+	/// Number of lock periods the voted balance stays locked after the vote closes.
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked2x => 1,
+			Conviction::Locked3x => 2,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 8,
+			Conviction::Locked6x => 16,
+		}
+	}
+
+	/// Votes `balance` counts for with this conviction.
+	pub fn votes<Balance: AtLeast32BitUnsigned>(self, balance: Balance) -> Balance {
+		let multiplier: u32 = match self {
+			Conviction::None => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 3,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 5,
+			Conviction::Locked6x => 6,
+		};
+		balance.saturating_mul(multiplier.into())
+	}
+}
@@ -3,13 +3,24 @@
 use codec::{Decode, Encode};
 use dispatch::DispatchResult;
 use frame_support::{
-	debug, decl_error, decl_event, decl_module, decl_storage, dispatch, ensure,
-	traits::{Currency, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
+	debug, decl_error, decl_event, decl_module, decl_storage, dispatch, ensure, Parameter,
+	dispatch::{DispatchResultWithPostInfo, PostDispatchInfo},
+	weights::GetDispatchInfo,
+	traits::{Currency, EnsureOrigin, Get, LockIdentifier, LockableCurrency, ReservableCurrency, WithdrawReasons},
+	weights::Weight,
 };
 use frame_system::{ensure_signed};
 use pallet_issue::Issue;
-use sp_runtime::RuntimeDebug;
-use sp_std::prelude::*;
+use sp_runtime::{RuntimeDebug, traits::{Dispatchable, Saturating, Zero}};
+use sp_std::{cmp::Ordering, prelude::*};
+
+mod vote_threshold;
+pub use vote_threshold::{TallyRule, VoteThreshold};
+mod conviction;
+pub use conviction::Conviction;
+mod migration;
+use migration::Releases;
+pub mod default_weights;
 
 #[cfg(test)]
 mod mock;
@@ -18,16 +29,80 @@ mod mock;
 mod tests;
 
 const GOV_ID: LockIdentifier = *b"issuegov";
+const PROPOSAL_LOCK_ID: LockIdentifier = *b"propsgov";
+const CONVICTION_ID: LockIdentifier = *b"convigov";
+
+pub trait WeightInfo {
+	fn reset_vote_period() -> Weight;
+	fn open_next() -> Weight;
+	fn vote() -> Weight;
+	fn over() -> Weight;
+	fn propose() -> Weight;
+	fn vote_proposal() -> Weight;
+	fn close_proposal() -> Weight;
+	fn unlock() -> Weight;
+	fn recover() -> Weight;
+	fn schedule_open() -> Weight;
+}
 
 pub trait Trait: pallet_timestamp::Trait {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
-	type Currency: LockableCurrency<Self::AccountId>;
+	type Currency: LockableCurrency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 
 	type Issue: Issue<BalanceOf<Self>>;
 
 	type VoteBlockTime: Get<Self::Moment>;
+
+	/// Tally rule of the votes releasing the next issue stage.
+	type IssueTallyRule: Get<TallyRule>;
+
+	/// Tally rule of the general proposals.
+	type ProposalTallyRule: Get<TallyRule>;
+
+	/// Deposit reserved from the proposer of a general proposal until it's closed.
+	type ProposalDeposit: Get<BalanceOf<Self>>;
+
+	/// Most general proposals closed in one block, the others are closed in the next ones.
+	type MaxClosesPerBlock: Get<u32>;
+
+	/// Duration of one conviction lock period, counted from the end of the vote.
+	type ConvictionLockPeriod: Get<Self::Moment>;
+
+	/// Origin which may open issue votes and change the vote period, next to the operate account.
+	type OpenOrigin: EnsureOrigin<<Self as frame_system::Trait>::Origin>;
+
+	/// Origin which may revive the issue schedule after a failed vote.
+	type RecoveryOrigin: EnsureOrigin<<Self as frame_system::Trait>::Origin>;
+
+	/// The outer origin type, passing proposals dispatch their call with `RawOrigin::Gov`.
+	type Origin: From<RawOrigin>;
+
+	/// The call a general proposal carries.
+	type Proposal: Parameter + Dispatchable<Origin = <Self as Trait>::Origin, PostInfo = PostDispatchInfo> + GetDispatchInfo;
+
+	/// Weight information for the extrinsics of this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+/// Origin of the calls dispatched by passing proposals.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+pub enum RawOrigin {
+	Gov,
+}
+
+pub type Origin = RawOrigin;
+
+/// Ensures an origin comes from a passing proposal.
+pub struct EnsureGov;
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>> EnsureOrigin<O> for EnsureGov {
+	type Success = ();
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Gov => Ok(()),
+		})
+	}
 }
 
 #[derive(Default, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
@@ -37,18 +112,73 @@ pub struct Proposal<Moment, Balance> {
 	end: Moment,
 	aye: Balance,
 	nay: Balance,
+	/// Balance which voted, without conviction
+	turnout: Balance,
+	rule: TallyRule,
+	/// Total issuance when the vote closed, unknown for the votes closed before it was recorded
+	electorate: Option<Balance>,
+	/// Records the recovery after the failed vote on `step` instead of a vote
+	recovery: bool,
 }
 
 #[derive(Default, PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct GovVote<Balance> {
 	pub aye: bool,
 	pub balance: Balance,
+	pub conviction: Conviction,
+}
+
+/// Proposal to dispatch `call`, open to votes from `start` to `end`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct GovProposal<AccountId, Moment, Balance, Call, Hash> {
+	pub proposer: AccountId,
+	/// Reserved from the proposer until the proposal is closed
+	pub deposit: Balance,
+	pub call: Call,
+	/// Weight of `call` when it was proposed, the bound `close_proposal` is called with must cover it
+	pub call_weight: Weight,
+	pub description: Hash,
+	pub start: Moment,
+	pub end: Moment,
+	pub aye: Balance,
+	pub nay: Balance,
+	pub turnout: Balance,
+	pub rule: TallyRule,
+}
+
+/// General proposal once closed, kept with its tally to check the result again.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct ClosedProposal<AccountId, Moment, Balance, Hash> {
+	pub proposer: AccountId,
+	pub description: Hash,
+	pub start: Moment,
+	pub end: Moment,
+	pub aye: Balance,
+	pub nay: Balance,
+	pub turnout: Balance,
+	pub rule: TallyRule,
+	/// Total issuance when the vote closed
+	pub electorate: Balance,
+	pub approved: bool,
 }
 
 pub type BalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 pub type MomentOf<T> = <T as pallet_timestamp::Trait>::Moment;
 pub type ProposalT<T> = Proposal<MomentOf<T>, BalanceOf<T>>;
+pub type GovProposalOf<T> = GovProposal<
+	<T as frame_system::Trait>::AccountId,
+	MomentOf<T>,
+	BalanceOf<T>,
+	<T as Trait>::Proposal,
+	<T as frame_system::Trait>::Hash,
+>;
+pub type ClosedProposalOf<T> = ClosedProposal<
+	<T as frame_system::Trait>::AccountId,
+	MomentOf<T>,
+	BalanceOf<T>,
+	<T as frame_system::Trait>::Hash,
+>;
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Gov {
@@ -59,6 +189,27 @@ decl_storage! {
 		HistoryProposals get(fn history_proposals): Vec<ProposalT<T>>;
 		Votes get(fn votes): double_map hasher(twox_64_concat) u32, hasher(twox_64_concat) T::AccountId => GovVote<BalanceOf<T>>;
 		VotingOf get(fn voting_of): map hasher(twox_64_concat) u32 => Vec<T::AccountId>;
+		/// Step and end of the issue vote an account holds its vote lock for, released once that vote closed
+		IssueVoteOf get(fn issue_vote_of): map hasher(twox_64_concat) T::AccountId => Option<(u32, MomentOf<T>)>;
+
+		/// Number of general proposals made so far, the next one gets this index
+		ProposalCount get(fn proposal_count): u32;
+		/// Open general proposals by index
+		GovProposals get(fn gov_proposals): map hasher(twox_64_concat) u32 => Option<GovProposalOf<T>>;
+		/// Indexes of the open general proposals by end, the earliest first
+		ProposalEnds get(fn proposal_ends): Vec<(MomentOf<T>, u32)>;
+		/// Closed general proposals by index
+		ClosedProposals get(fn closed_proposals): map hasher(twox_64_concat) u32 => Option<ClosedProposalOf<T>>;
+		ProposalVotes get(fn proposal_votes): double_map hasher(twox_64_concat) u32, hasher(twox_64_concat) T::AccountId => GovVote<BalanceOf<T>>;
+		ProposalVoters get(fn proposal_voters): map hasher(twox_64_concat) u32 => Vec<T::AccountId>;
+		/// General proposals an account voted on and holds its lock for, released once they closed
+		VotedProposals get(fn voted_proposals): map hasher(twox_64_concat) T::AccountId => Vec<u32>;
+		/// Balances kept locked after closed votes for their conviction, until the given moment
+		ConvictionLocks get(fn conviction_locks): map hasher(twox_64_concat) T::AccountId => Vec<(MomentOf<T>, BalanceOf<T>)>;
+		/// Moment from which the next issue vote opens on its own
+		ScheduledOpen get(fn scheduled_open): Option<MomentOf<T>>;
+		/// Storage version of the pallet, new networks start on the latest one
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V2_0_0): Releases;
 	}
 }
 
@@ -66,11 +217,19 @@ decl_event!(
 	pub enum Event<T>
 	where
 		AccountId = <T as frame_system::Trait>::AccountId,
+		Hash = <T as frame_system::Trait>::Hash,
 		Balance = BalanceOf<T>,
+		Moment = MomentOf<T>,
 	{
 		OpenProposal(u32),                    // step
 		CloseProposal(u32, bool),             // step, state
 		Voted(u32, AccountId, bool, Balance), // step, account, t/f,  mount
+		Proposed(u32, AccountId, Hash),               // index, proposer, description
+		ProposalVoted(u32, AccountId, bool, Balance), // index, account, t/f, amount
+		ProposalClosed(u32, bool),                    // index, state
+		ProposalExecuted(u32, DispatchResult),        // index, result of the call
+		OpenScheduled(Moment),                        // moment the next issue vote opens
+		Recovered(u32, bool),                         // failed step, rerun
 	}
 );
 
@@ -85,54 +244,84 @@ decl_error! {
 		OutdatedProposal,
 		VoteDiffCamps,
 		NotOperateAccount,
+		NotBroken,
+		WrongProposalWeight,
 	}
 }
 
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+	pub struct Module<T: Trait> for enum Call where origin: <T as frame_system::Trait>::Origin {
 		// Errors must be initialized if they are used by the pallet.
 		type Error = Error<T>;
 
 		// Events must be initialized if they are used by the pallet.
 		fn deposit_event() = default;
 
-		#[weight = 0]
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get() == Releases::V1_0_0 {
+				migration::migrate_to_v2::<T>()
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		/// Opens a scheduled issue vote and closes the votes which are over,
+		/// at most `MaxClosesPerBlock` of the general proposals.
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads(4);
+			if let Some(at) = <ScheduledOpen<T>>::get() {
+				if at <= <pallet_timestamp::Module<T>>::get() && Alive::get() && <OpenedProposal<T>>::get().is_none() {
+					<ScheduledOpen<T>>::kill();
+					Self::open_next_stage(<VotePeriod<T>>::get(), T::IssueTallyRule::get());
+					weight = weight.saturating_add(<T as Trait>::WeightInfo::open_next());
+				}
+			}
+			if let Some(prop) = <OpenedProposal<T>>::get() {
+				if Self::is_finish(prop.end) {
+					Self::close_issue_vote(prop);
+					weight = weight.saturating_add(<T as Trait>::WeightInfo::over());
+				}
+			}
+			let mut ends = <ProposalEnds<T>>::get();
+			let finished = ends.iter()
+				.take(T::MaxClosesPerBlock::get() as usize)
+				.take_while(|(end, _)| Self::is_finish(*end))
+				.count();
+			if finished > 0 {
+				for (_, index) in ends.drain(..finished) {
+					if let Some(prop) = <GovProposals<T>>::get(index) {
+						let call_weight = Self::close_gov_proposal(index, prop);
+						weight = weight
+							.saturating_add(<T as Trait>::WeightInfo::close_proposal())
+							.saturating_add(call_weight);
+					}
+				}
+				<ProposalEnds<T>>::put(ends);
+			}
+			weight
+		}
+
+		#[weight = <T as Trait>::WeightInfo::reset_vote_period()]
 		pub fn reset_vote_period(origin, period: MomentOf<T>) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			Self::ensure_operator(origin)?;
 
-			//verify operate account
-			ensure!(Self::verify_operate_account(who), Error::<T>::NotOperateAccount);
-			
 			<VotePeriod<T>>::set(period);
 			Ok(())
 		}
 
-		#[weight = 0]
+		#[weight = <T as Trait>::WeightInfo::open_next()]
 		pub fn open_next(origin) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-
-			//verify operate account
-			ensure!(Self::verify_operate_account(who), Error::<T>::NotOperateAccount);
+			Self::ensure_operator(origin)?;
 
 			ensure!(Alive::get(), Error::<T>::BrokenConsensus);
 			ensure!(<OpenedProposal<T>>::get().is_none(), Error::<T>::UncloseProposal);
 
-			let next_step = CurrentStep::get() + 1;
-			let start_time = <pallet_timestamp::Module<T>>::get();
-			let end_time = <pallet_timestamp::Module<T>>::get() + <VotePeriod<T>>::get();
-			let proposal = Proposal {
-				step: next_step,
-				start: start_time,
-				end: end_time,
-				..Default::default()
-			};
-
-			<OpenedProposal<T>>::set(Some(proposal));
-			Self::deposit_event(RawEvent::OpenProposal(next_step));
+			Self::open_next_stage(<VotePeriod<T>>::get(), T::IssueTallyRule::get());
 			Ok(())
 		}
 
-		#[weight = 0]
+		/// Votes on the open issue vote, locking the balance until it's closed and `unlock` is called.
+		#[weight = <T as Trait>::WeightInfo::vote()]
 		pub fn vote(origin, vote: GovVote<BalanceOf<T>>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -140,38 +329,39 @@ decl_module! {
 			let mut prop = <OpenedProposal<T>>::get().ok_or(Error::<T>::UnExistedProposal)?;
 			ensure!(!Self::is_finish(prop.end), Error::<T>::OutdatedProposal);
 
-			if <Votes<T>>::contains_key(&prop.step, &who) {
-				let mut s_vote = <Votes<T>>::get(&prop.step, &who);
-				ensure!(s_vote.aye == vote.aye, Error::<T>::VoteDiffCamps);
-				s_vote.balance += vote.balance;
-				ensure!(s_vote.balance <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
-				<Votes<T>>::insert(prop.step, &who, &s_vote);
-
-				T::Currency::extend_lock(
-					GOV_ID,
-					&who,
-					s_vote.balance,
-					WithdrawReasons::all()
-				);
-			} else {
-				ensure!(vote.balance <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
-				<Votes<T>>::insert(prop.step, &who, &vote);
-				<VotingOf<T>>::mutate(prop.step, |person| person.push(who.clone()));
-
-				T::Currency::set_lock(
-					GOV_ID,
-					&who,
-					vote.balance,
-					WithdrawReasons::all()
-				);
+			let voted = Self::issue_vote_of(&who) == Some((prop.step, prop.end));
+			let mut s_vote = vote.clone();
+			let mut old_votes: BalanceOf<T> = Zero::zero();
+			if voted {
+				let prev = <Votes<T>>::get(prop.step, &who);
+				ensure!(prev.aye == vote.aye, Error::<T>::VoteDiffCamps);
+				old_votes = prev.conviction.votes(prev.balance);
+				s_vote.balance += prev.balance;
 			}
+			ensure!(s_vote.balance <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
 
-			// change total.
+			if !voted {
+				// the lock of an earlier issue vote is released first
+				Self::release_issue_vote(&who);
+				<VotingOf<T>>::append(prop.step, &who);
+				<IssueVoteOf<T>>::insert(&who, (prop.step, prop.end));
+			}
+			<Votes<T>>::insert(prop.step, &who, &s_vote);
+			T::Currency::set_lock(
+				GOV_ID,
+				&who,
+				s_vote.balance,
+				WithdrawReasons::all()
+			);
+
+			// change total, the latest conviction applies to the whole vote.
+			let new_votes = s_vote.conviction.votes(s_vote.balance);
 			if vote.aye {
-				prop.aye += vote.balance;
+				prop.aye = prop.aye.saturating_sub(old_votes).saturating_add(new_votes);
 			} else {
-				prop.nay += vote.balance;
+				prop.nay = prop.nay.saturating_sub(old_votes).saturating_add(new_votes);
 			}
+			prop.turnout += vote.balance;
 
 			// change storage
 			<OpenedProposal<T>>::put(&prop);
@@ -180,56 +370,319 @@ decl_module! {
 			Ok(())
 		}
 
-		#[weight = 0]
+		/// Closes the issue vote once it's over, any account may call it.
+		#[weight = <T as Trait>::WeightInfo::over()]
 		pub fn over(origin) -> DispatchResult {
-			let _sender = ensure_signed(origin)?;
-			//verify operate account
-			ensure!(Self::verify_operate_account(_sender), Error::<T>::NotOperateAccount);
+			ensure_signed(origin)?;
 
 			ensure!(Alive::get(), Error::<T>::BrokenConsensus);
 			let prop = <OpenedProposal<T>>::get().ok_or(Error::<T>::UnExistedProposal)?;
 			ensure!(Self::is_finish(prop.end), Error::<T>::UnfinishedProposal);
 
-			let aye = Self::judge(&prop);
-			if aye {
-				Self::inject();
-				CurrentStep::set(prop.step);
+			Self::close_issue_vote(prop);
+			Ok(())
+		}
+
+		/// Opens a vote on dispatching `call` with the gov origin, next to any other open proposal.
+		/// `ProposalDeposit` is reserved from the proposer until it's closed.
+		#[weight = <T as Trait>::WeightInfo::propose()]
+		pub fn propose(origin, call: Box<<T as Trait>::Proposal>, description: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let deposit = T::ProposalDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientFunds)?;
+
+			let index = ProposalCount::get();
+			let start = <pallet_timestamp::Module<T>>::get();
+			let end = start + <VotePeriod<T>>::get();
+			let proposal = GovProposal {
+				proposer: who.clone(),
+				deposit,
+				call_weight: call.get_dispatch_info().weight,
+				call: *call,
+				description,
+				start,
+				end,
+				aye: Zero::zero(),
+				nay: Zero::zero(),
+				turnout: Zero::zero(),
+				rule: T::ProposalTallyRule::get(),
+			};
+
+			ProposalCount::put(index + 1);
+			<GovProposals<T>>::insert(index, proposal);
+			<ProposalEnds<T>>::mutate(|ends| {
+				// after the proposals with the same end
+				let pos = ends.binary_search_by(|(e, _)| e.cmp(&end).then(Ordering::Less)).unwrap_or_else(|pos| pos);
+				ends.insert(pos, (end, index));
+			});
+			Self::deposit_event(RawEvent::Proposed(index, who, description));
+			Ok(())
+		}
+
+		/// Votes on the general proposal `index`, locking the balance until it's closed and `unlock` is called.
+		#[weight = <T as Trait>::WeightInfo::vote_proposal()]
+		pub fn vote_proposal(origin, index: u32, vote: GovVote<BalanceOf<T>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut prop = <GovProposals<T>>::get(index).ok_or(Error::<T>::UnExistedProposal)?;
+			ensure!(!Self::is_finish(prop.end), Error::<T>::OutdatedProposal);
+
+			let voted = <ProposalVotes<T>>::contains_key(index, &who);
+			let mut s_vote = vote.clone();
+			let mut old_votes: BalanceOf<T> = Zero::zero();
+			if voted {
+				let prev = <ProposalVotes<T>>::get(index, &who);
+				ensure!(prev.aye == vote.aye, Error::<T>::VoteDiffCamps);
+				old_votes = prev.conviction.votes(prev.balance);
+				s_vote.balance += prev.balance;
+			}
+			ensure!(s_vote.balance <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
+
+			// the votes on closed proposals are released first
+			Self::release_proposal_votes(&who);
+			if !voted {
+				<ProposalVoters<T>>::append(index, &who);
+				<VotedProposals<T>>::append(&who, index);
+			}
+			<ProposalVotes<T>>::insert(index, &who, &s_vote);
+			T::Currency::extend_lock(
+				PROPOSAL_LOCK_ID,
+				&who,
+				s_vote.balance,
+				WithdrawReasons::all()
+			);
+
+			let new_votes = s_vote.conviction.votes(s_vote.balance);
+			if vote.aye {
+				prop.aye = prop.aye.saturating_sub(old_votes).saturating_add(new_votes);
 			} else {
-				// broken
-				Alive::put(false);
+				prop.nay = prop.nay.saturating_sub(old_votes).saturating_add(new_votes);
 			}
-			// unlock vote.
-			for person in <VotingOf<T>>::get(prop.step) {
-				T::Currency::remove_lock(
-					GOV_ID,
-					&person,
-				);
+			prop.turnout += vote.balance;
+			<GovProposals<T>>::insert(index, &prop);
+
+			Self::deposit_event(RawEvent::ProposalVoted(index, who, vote.aye, vote.balance));
+			Ok(())
+		}
+
+		/// Closes the general proposal `index` once its vote is over, dispatching its call if it passed.
+		/// Any account may call it, `weight_bound` must cover the weight of the call.
+		#[weight = <T as Trait>::WeightInfo::close_proposal().saturating_add(*weight_bound)]
+		pub fn close_proposal(origin, index: u32, #[compact] weight_bound: Weight) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let prop = <GovProposals<T>>::get(index).ok_or(Error::<T>::UnExistedProposal)?;
+			ensure!(Self::is_finish(prop.end), Error::<T>::UnfinishedProposal);
+			ensure!(prop.call_weight <= weight_bound, Error::<T>::WrongProposalWeight);
+
+			<ProposalEnds<T>>::mutate(|ends| ends.retain(|(_, i)| *i != index));
+			let call_weight = Self::close_gov_proposal(index, prop);
+			Ok(Some(<T as Trait>::WeightInfo::close_proposal().saturating_add(call_weight)).into())
+		}
+
+		/// Releases the locks of `target` for the votes which closed, keeping the balance voted with
+		/// conviction locked for its periods, and the conviction locks whose periods are over.
+		#[weight = <T as Trait>::WeightInfo::unlock()]
+		pub fn unlock(origin, target: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::release_issue_vote(&target);
+			Self::release_proposal_votes(&target);
+
+			let now = <pallet_timestamp::Module<T>>::get();
+			let mut locks = <ConvictionLocks<T>>::take(&target);
+			locks.retain(|(until, _)| *until > now);
+			match locks.iter().map(|(_, balance)| *balance).max() {
+				Some(balance) => {
+					T::Currency::set_lock(CONVICTION_ID, &target, balance, WithdrawReasons::all());
+					<ConvictionLocks<T>>::insert(&target, locks);
+				},
+				None => T::Currency::remove_lock(CONVICTION_ID, &target),
 			}
+			Ok(())
+		}
 
-			<OpenedProposal<T>>::set(None);
-			<HistoryProposals<T>>::mutate(|props| props.push(prop.clone()));
-			Self::deposit_event(RawEvent::CloseProposal(prop.step, aye));
+		/// Revives the issue schedule after a failed vote, recording it in `HistoryProposals`.
+		///
+		/// With `rerun` the vote on the failed step opens again, for `period` and with `rule`
+		/// when given instead of the usual vote period and tally rule.
+		#[weight = <T as Trait>::WeightInfo::recover()]
+		pub fn recover(origin, rerun: bool, period: Option<MomentOf<T>>, rule: Option<TallyRule>) -> DispatchResult {
+			T::RecoveryOrigin::ensure_origin(origin)?;
+			ensure!(!Alive::get(), Error::<T>::NotBroken);
+
+			let step = CurrentStep::get() + 1;
+			let now = <pallet_timestamp::Module<T>>::get();
+			<HistoryProposals<T>>::mutate(|props| props.push(Proposal {
+				step,
+				start: now,
+				end: now,
+				recovery: true,
+				..Default::default()
+			}));
+			Alive::put(true);
+
+			if rerun {
+				Self::open_next_stage(
+					period.unwrap_or_else(<VotePeriod<T>>::get),
+					rule.unwrap_or_else(T::IssueTallyRule::get),
+				);
+			}
+			Self::deposit_event(RawEvent::Recovered(step, rerun));
 			Ok(())
 		}
 
+		/// Opens the next issue vote on its own from `at`, unless one is open by then.
+		#[weight = <T as Trait>::WeightInfo::schedule_open()]
+		pub fn schedule_open(origin, at: MomentOf<T>) -> DispatchResult {
+			Self::ensure_operator(origin)?;
+
+			<ScheduledOpen<T>>::put(at);
+			Self::deposit_event(RawEvent::OpenScheduled(at));
+			Ok(())
+		}
 	}
 }
 
 impl<T: Trait> Module<T> {
+	fn open_next_stage(period: MomentOf<T>, rule: TallyRule) {
+		let next_step = CurrentStep::get() + 1;
+		let start_time = <pallet_timestamp::Module<T>>::get();
+		let end_time = <pallet_timestamp::Module<T>>::get() + period;
+		let proposal = Proposal {
+			step: next_step,
+			start: start_time,
+			end: end_time,
+			rule,
+			..Default::default()
+		};
+
+		<OpenedProposal<T>>::set(Some(proposal));
+		Self::deposit_event(RawEvent::OpenProposal(next_step));
+	}
+
+	/// Closes the issue vote, its voters release their locks through `unlock`
+	fn close_issue_vote(mut prop: ProposalT<T>) {
+		let electorate = T::Currency::total_issuance();
+		prop.electorate = Some(electorate);
+		let aye = prop.rule.approved(prop.aye, prop.nay, prop.turnout, electorate);
+		if aye {
+			Self::inject();
+			CurrentStep::set(prop.step);
+		} else {
+			// broken
+			Alive::put(false);
+		}
+
+		<OpenedProposal<T>>::set(None);
+		<HistoryProposals<T>>::mutate(|props| props.push(prop.clone()));
+		Self::deposit_event(RawEvent::CloseProposal(prop.step, aye));
+	}
+
+	/// Closes the general proposal `index`, returns the actual weight of its call if it was dispatched.
+	/// Its voters release their locks through `unlock`, it's up to the caller to drop it from `ProposalEnds`.
+	fn close_gov_proposal(index: u32, prop: GovProposalOf<T>) -> Weight {
+		<GovProposals<T>>::remove(index);
+		T::Currency::unreserve(&prop.proposer, prop.deposit);
+
+		let electorate = T::Currency::total_issuance();
+		let aye = prop.rule.approved(prop.aye, prop.nay, prop.turnout, electorate);
+		<ClosedProposals<T>>::insert(index, ClosedProposal {
+			proposer: prop.proposer,
+			description: prop.description,
+			start: prop.start,
+			end: prop.end,
+			aye: prop.aye,
+			nay: prop.nay,
+			turnout: prop.turnout,
+			rule: prop.rule,
+			electorate,
+			approved: aye,
+		});
+		Self::deposit_event(RawEvent::ProposalClosed(index, aye));
+		if !aye {
+			return 0;
+		}
+
+		let info = prop.call.get_dispatch_info();
+		let result = prop.call.dispatch(RawOrigin::Gov.into());
+		let post_info = match &result {
+			Ok(post_info) => *post_info,
+			Err(err) => err.post_info,
+		};
+		Self::deposit_event(RawEvent::ProposalExecuted(index, result.map(|_| ()).map_err(|e| e.error)));
+		post_info.calc_actual_weight(&info)
+	}
+
+	/// Checks `origin` is `OpenOrigin` or the operate account
+	fn ensure_operator(origin: <T as frame_system::Trait>::Origin) -> DispatchResult {
+		if let Err(origin) = T::OpenOrigin::try_origin(origin) {
+			let who = ensure_signed(origin)?;
+			//verify operate account
+			ensure!(Self::verify_operate_account(who), Error::<T>::NotOperateAccount);
+		}
+		Ok(())
+	}
+
 	fn inject() {
 		// update issue stage.
 		T::Issue::consume();
 	}
 
+	/// Releases the lock of `who` for its issue vote once that vote closed
+	fn release_issue_vote(who: &T::AccountId) {
+		if let Some((step, end)) = <IssueVoteOf<T>>::get(who) {
+			let open = <OpenedProposal<T>>::get().map_or(false, |prop| prop.step == step && prop.end == end);
+			if !open {
+				<IssueVoteOf<T>>::remove(who);
+				T::Currency::remove_lock(GOV_ID, who);
+				Self::lock_conviction(who, &<Votes<T>>::get(step, who), end);
+			}
+		}
+	}
+
+	/// Releases the votes of `who` on the closed general proposals, shrinking its lock to its
+	/// largest vote on the open ones
+	fn release_proposal_votes(who: &T::AccountId) {
+		let mut open = Vec::new();
+		let mut locked: BalanceOf<T> = Zero::zero();
+		for index in <VotedProposals<T>>::take(who) {
+			match <ClosedProposals<T>>::get(index) {
+				Some(closed) => Self::lock_conviction(who, &<ProposalVotes<T>>::take(index, who), closed.end),
+				None => {
+					locked = locked.max(<ProposalVotes<T>>::get(index, who).balance);
+					open.push(index);
+				},
+			}
+		}
+		if open.is_empty() {
+			T::Currency::remove_lock(PROPOSAL_LOCK_ID, who);
+		} else {
+			T::Currency::set_lock(PROPOSAL_LOCK_ID, who, locked, WithdrawReasons::all());
+			<VotedProposals<T>>::insert(who, open);
+		}
+	}
+
+	/// Keeps the balance of a vote which ended at `end` locked for the periods of its conviction
+	fn lock_conviction(who: &T::AccountId, vote: &GovVote<BalanceOf<T>>, end: MomentOf<T>) {
+		let periods = vote.conviction.lock_periods();
+		if periods == 0 || vote.balance.is_zero() {
+			return;
+		}
+		let until = end.saturating_add(T::ConvictionLockPeriod::get().saturating_mul(periods.into()));
+		if until <= <pallet_timestamp::Module<T>>::get() {
+			return;
+		}
+		<ConvictionLocks<T>>::append(who, (until, vote.balance));
+		T::Currency::extend_lock(CONVICTION_ID, who, vote.balance, WithdrawReasons::all());
+	}
+
 	fn is_finish(end: MomentOf<T>) -> bool {
 		let now = <pallet_timestamp::Module<T>>::get();
 		end < now
 	}
 
-	fn judge(props: &ProposalT<T>) -> bool {
-		props.aye > props.nay
-	}
-
 	fn verify_operate_account(sender: T::AccountId) -> bool {
 		//get operate account from pallet issue
 		let account_vu8 = T::Issue::get_operate_account();
@@ -0,0 +1,88 @@
+//! Storage migrations of the gov pallet.
+
+use codec::{Decode, Encode};
+use frame_support::{IterableStorageDoubleMap, StorageValue, traits::Get, weights::Weight};
+use sp_runtime::{RuntimeDebug, traits::Saturating};
+use sp_std::{cell::Cell, prelude::*};
+
+use crate::{
+	BalanceOf, Conviction, GovVote, HistoryProposals, IssueVoteOf, MomentOf, OpenedProposal, Proposal, ProposalT, StorageVersion, TallyRule,
+	Trait, Votes, VotingOf,
+};
+
+/// Version of the gov storage, checked by `on_runtime_upgrade` to run the migrations it's behind.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	/// Issue votes without tally rule nor turnout
+	V1_0_0,
+	/// Issue votes record their tally rule, turnout, electorate and recoveries, votes their conviction
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Issue vote as stored before `Releases::V2_0_0`
+#[derive(Decode)]
+struct OldProposal<Moment, Balance> {
+	step: u32,
+	start: Moment,
+	end: Moment,
+	aye: Balance,
+	nay: Balance,
+}
+
+impl<Moment, Balance: Saturating + Copy + Default> OldProposal<Moment, Balance> {
+	/// The votes were counted under a simple majority, without conviction. The electorate wasn't
+	/// recorded, so their result can't be checked again.
+	fn upgraded(self) -> Proposal<Moment, Balance> {
+		Proposal {
+			step: self.step,
+			start: self.start,
+			end: self.end,
+			aye: self.aye,
+			nay: self.nay,
+			turnout: self.aye.saturating_add(self.nay),
+			rule: TallyRule::default(),
+			electorate: None,
+			recovery: false,
+		}
+	}
+}
+
+/// Vote on an issue vote as stored before `Releases::V2_0_0`
+#[derive(Decode)]
+struct OldGovVote<Balance> {
+	aye: bool,
+	balance: Balance,
+}
+
+/// Moves the issue votes and their votes to `Releases::V2_0_0`, the open one keeps the rule it was opened under.
+/// Its voters get the record `unlock` releases their lock by once it's closed.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+	let _ = <OpenedProposal<T>>::translate(|prop: Option<OldProposal<MomentOf<T>, BalanceOf<T>>>| {
+		prop.map(OldProposal::upgraded)
+	});
+	let _ = <HistoryProposals<T>>::translate(|props: Option<Vec<OldProposal<MomentOf<T>, BalanceOf<T>>>>| {
+		props.map(|props| props.into_iter().map(OldProposal::upgraded).collect::<Vec<ProposalT<T>>>())
+	});
+	let mut voters = 0u64;
+	if let Some(prop) = <OpenedProposal<T>>::get() {
+		for who in <VotingOf<T>>::get(prop.step) {
+			<IssueVoteOf<T>>::insert(&who, (prop.step, prop.end));
+			voters += 1;
+		}
+	}
+	let votes = Cell::new(0u64);
+	<Votes<T>>::translate(|_, _, vote: OldGovVote<BalanceOf<T>>| {
+		votes.set(votes.get() + 1);
+		Some(GovVote { aye: vote.aye, balance: vote.balance, conviction: Conviction::None })
+	});
+	StorageVersion::put(Releases::V2_0_0);
+
+	let votes = votes.get();
+	T::DbWeight::get().reads_writes(votes.saturating_add(5), votes.saturating_add(voters).saturating_add(3))
+}
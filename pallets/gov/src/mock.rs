@@ -1,15 +1,54 @@
-use crate::{Module, Trait};
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use crate::{EnsureGov, Module, TallyRule, Trait, VoteThreshold};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchResultWithPostInfo, PostDispatchInfo},
+	impl_outer_origin, parameter_types,
+	traits::EnsureOrigin,
+	weights::{DispatchInfo, GetDispatchInfo, Weight},
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Dispatchable, IdentityLookup},
 	Perbill,
 };
+use std::cell::RefCell;
+
+mod gov {
+	pub use crate::Origin;
+}
 
 impl_outer_origin! {
-	pub enum Origin for Test {}
+	pub enum Origin for Test {
+		gov,
+	}
+}
+
+thread_local! {
+	pub static GOV_VALUE: RefCell<u64> = RefCell::new(0);
+}
+
+/// Proposal call setting `GOV_VALUE`, only the gov origin may dispatch it.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug)]
+pub struct SetValue(pub u64);
+
+impl Dispatchable for SetValue {
+	type Origin = Origin;
+	type Trait = ();
+	type Info = ();
+	type PostInfo = PostDispatchInfo;
+	fn dispatch(self, origin: Origin) -> DispatchResultWithPostInfo {
+		EnsureGov::ensure_origin(origin)?;
+		GOV_VALUE.with(|v| *v.borrow_mut() = self.0);
+		Ok(().into())
+	}
+}
+
+impl GetDispatchInfo for SetValue {
+	fn get_dispatch_info(&self) -> DispatchInfo {
+		DispatchInfo { weight: 100, ..Default::default() }
+	}
 }
 
 // Configure a mock runtime to test the pallet.
@@ -54,6 +93,9 @@ impl system::Trait for Test {
 parameter_types! {
 	pub const MinimumPeriod: u64 = 5;
 	pub const VoteBlockTime: u64 = 2;
+	pub const ConvictionLockPeriod: u64 = 10;
+	pub const ProposalDeposit: u64 = 5;
+	pub const MaxClosesPerBlock: u32 = 2;
 	pub const ExistentialDeposit: u64 = 1;
 }
 impl pallet_timestamp::Trait for Test {
@@ -78,11 +120,32 @@ impl pallet_issue::Trait for Test {
 	type Currency = Balances;
 }
 
+parameter_types! {
+	pub IssueTallyRule: TallyRule = TallyRule {
+		turnout: Perbill::from_percent(20),
+		threshold: VoteThreshold::SuperMajority(Perbill::from_percent(66)),
+	};
+	pub ProposalTallyRule: TallyRule = TallyRule {
+		turnout: Perbill::from_percent(20),
+		threshold: VoteThreshold::SimpleMajority,
+	};
+}
+
 impl Trait for Test {
 	type Event = ();
 	type Currency = Balances;
 	type Issue = Issue;
 	type VoteBlockTime = VoteBlockTime;
+	type IssueTallyRule = IssueTallyRule;
+	type ProposalTallyRule = ProposalTallyRule;
+	type ProposalDeposit = ProposalDeposit;
+	type MaxClosesPerBlock = MaxClosesPerBlock;
+	type ConvictionLockPeriod = ConvictionLockPeriod;
+	type OpenOrigin = frame_system::EnsureRoot<u64>;
+	type RecoveryOrigin = frame_system::EnsureRoot<u64>;
+	type Origin = Origin;
+	type Proposal = SetValue;
+	type WeightInfo = ();
 }
 
 // pub type System = frame_system::Module<Test>;
@@ -1,7 +1,9 @@
 use crate::{mock::*, Error};
-use crate::{GovVote, Proposal};
-use frame_support::{assert_noop, assert_ok};
+use crate::{Conviction, GovVote, Proposal, TallyRule, VoteThreshold};
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap, StorageMap, StorageValue, storage::unhashed, traits::{OnInitialize, OnRuntimeUpgrade}};
 use pallet_balances::Error as BalancesError;
+use sp_core::H256;
+use sp_runtime::{Perbill, traits::{BadOrigin, Dispatchable}};
 
 fn fast_forward_to(n: u64) {
 	Timestamp::set_timestamp(n);
@@ -50,6 +52,7 @@ fn vote_ok_and_lock_balance() {
 		let vt1 = GovVote {
 			aye: true,
 			balance: 60,
+			conviction: Conviction::None,
 		};
 		assert_noop!(
 			GovModule::vote(Origin::signed(10), vt1),
@@ -58,6 +61,7 @@ fn vote_ok_and_lock_balance() {
 		let mut vt2 = GovVote {
 			aye: true,
 			balance: 40,
+			conviction: Conviction::None,
 		};
 		assert_ok!(GovModule::vote(Origin::signed(10), vt2.clone()));
 		assert_noop!(
@@ -80,6 +84,7 @@ fn vote_ok_and_lock_balance() {
 		let vt3 = GovVote {
 			aye: false,
 			balance: 10,
+			conviction: Conviction::None,
 		};
 		assert_ok!(GovModule::vote(Origin::signed(20), vt3));
 		let proposal = Proposal {
@@ -88,6 +93,10 @@ fn vote_ok_and_lock_balance() {
 			end: GovModule::vote_period(),
 			aye: 50,
 			nay: 10,
+			turnout: 60,
+			rule: IssueTallyRule::get(),
+			electorate: None,
+			recovery: false,
 		};
 		assert_eq!(GovModule::opened_proposal(), Some(proposal));
 	});
@@ -102,7 +111,8 @@ fn close_proposal_and_unlock_balance() {
 			Origin::signed(10),
 			GovVote {
 				aye: false,
-				balance: 50
+				balance: 50,
+				conviction: Conviction::None,
 			}
 		));
 		assert_noop!(
@@ -114,7 +124,8 @@ fn close_proposal_and_unlock_balance() {
 			Origin::signed(20),
 			GovVote {
 				aye: false,
-				balance: 50
+				balance: 50,
+				conviction: Conviction::None,
 			}
 		));
 		assert_noop!(
@@ -125,6 +136,14 @@ fn close_proposal_and_unlock_balance() {
 		fast_forward_to(5);
 
 		assert_ok!(GovModule::over(Origin::signed(10)));
+		// locked until released
+		assert_noop!(
+			Balances::transfer(Origin::signed(10), 30, 1),
+			BalancesError::<Test, _>::LiquidityRestrictions
+		);
+		assert_ok!(GovModule::unlock(Origin::signed(30), 10));
+		assert_ok!(GovModule::unlock(Origin::signed(30), 20));
+		assert!(GovModule::issue_vote_of(10).is_none());
 		assert_ok!(Balances::transfer(Origin::signed(10), 30, 1));
 		assert_ok!(Balances::transfer(Origin::signed(20), 30, 1));
 	});
@@ -138,7 +157,8 @@ fn abandoned_proposal_should_broken() {
 			Origin::signed(10),
 			GovVote {
 				aye: false,
-				balance: 50
+				balance: 50,
+				conviction: Conviction::None,
 			}
 		));
 		fast_forward_to(5);
@@ -161,7 +181,8 @@ fn pass_proposal_and_can_open_next() {
 			Origin::signed(10),
 			GovVote {
 				aye: true,
-				balance: 50
+				balance: 50,
+				conviction: Conviction::None,
 			}
 		));
 		fast_forward_to(5);
@@ -173,7 +194,8 @@ fn pass_proposal_and_can_open_next() {
 			Origin::signed(10),
 			GovVote {
 				aye: true,
-				balance: 50
+				balance: 50,
+				conviction: Conviction::None,
 			}
 		));
 		fast_forward_to(10);
@@ -190,7 +212,8 @@ fn pass_and_comsue() {
 			Origin::signed(10),
 			GovVote {
 				aye: true,
-				balance: 50
+				balance: 50,
+				conviction: Conviction::None,
 			}
 		));
 		fast_forward_to(5);
@@ -200,3 +223,398 @@ fn pass_and_comsue() {
 		assert_eq!(Issue::remain(), 100);
 	});
 }
+
+#[test]
+fn proposals_run_side_by_side() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			GovModule::propose(Origin::signed(40), Box::new(SetValue(7)), H256::zero()),
+			Error::<Test>::InsufficientFunds
+		);
+		assert_ok!(GovModule::propose(Origin::signed(30), Box::new(SetValue(7)), H256::zero()));
+		assert_ok!(GovModule::propose(Origin::signed(30), Box::new(SetValue(9)), H256::zero()));
+		assert_eq!(GovModule::proposal_count(), 2);
+		assert_eq!(Balances::reserved_balance(30), 10);
+
+		assert_ok!(GovModule::vote_proposal(Origin::signed(10), 0, GovVote { aye: true, balance: 30, conviction: Conviction::None }));
+		assert_ok!(GovModule::vote_proposal(Origin::signed(10), 1, GovVote { aye: false, balance: 20, conviction: Conviction::None }));
+		assert_ok!(GovModule::vote_proposal(Origin::signed(20), 1, GovVote { aye: true, balance: 40, conviction: Conviction::None }));
+		assert_noop!(
+			GovModule::vote_proposal(Origin::signed(20), 2, GovVote { aye: true, balance: 40, conviction: Conviction::None }),
+			Error::<Test>::UnExistedProposal
+		);
+		// the lock covers the largest vote
+		assert_noop!(
+			Balances::transfer(Origin::signed(10), 30, 21),
+			BalancesError::<Test, _>::LiquidityRestrictions
+		);
+		assert_noop!(
+			GovModule::close_proposal(Origin::signed(10), 0, 100),
+			Error::<Test>::UnfinishedProposal
+		);
+
+		fast_forward_to(5);
+		assert_ok!(GovModule::close_proposal(Origin::signed(10), 0, 100));
+		assert_eq!(GOV_VALUE.with(|v| *v.borrow()), 7);
+		assert_eq!(GovModule::gov_proposals(0), None);
+		// still locked for the other proposal once released
+		assert_ok!(GovModule::unlock(Origin::signed(10), 10));
+		assert_eq!(GovModule::voted_proposals(10), vec![1]);
+		assert_ok!(Balances::transfer(Origin::signed(10), 30, 30));
+		assert_noop!(
+			Balances::transfer(Origin::signed(10), 30, 1),
+			BalancesError::<Test, _>::LiquidityRestrictions
+		);
+
+		assert_ok!(GovModule::close_proposal(Origin::signed(10), 1, 100));
+		assert_eq!(GOV_VALUE.with(|v| *v.borrow()), 9);
+		assert_eq!(Balances::reserved_balance(30), 0);
+		assert_ok!(GovModule::unlock(Origin::signed(10), 10));
+		assert_ok!(GovModule::unlock(Origin::signed(10), 20));
+		assert!(GovModule::voted_proposals(10).is_empty());
+		assert_ok!(Balances::transfer(Origin::signed(10), 30, 10));
+		assert_ok!(Balances::transfer(Origin::signed(20), 30, 10));
+	});
+}
+
+#[test]
+fn rejected_proposal_is_not_dispatched() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(GovModule::propose(Origin::signed(10), Box::new(SetValue(7)), H256::zero()));
+		assert_ok!(GovModule::vote_proposal(Origin::signed(20), 0, GovVote { aye: false, balance: 10, conviction: Conviction::None }));
+		fast_forward_to(5);
+		assert_noop!(
+			GovModule::vote_proposal(Origin::signed(10), 0, GovVote { aye: true, balance: 10, conviction: Conviction::None }),
+			Error::<Test>::OutdatedProposal
+		);
+		assert_ok!(GovModule::close_proposal(Origin::signed(10), 0, 100));
+		assert_eq!(GOV_VALUE.with(|v| *v.borrow()), 0);
+
+		// archived with its tally
+		let closed = GovModule::closed_proposals(0).unwrap();
+		assert_eq!((closed.proposer, closed.nay, closed.turnout), (10, 10, 10));
+		assert_eq!((closed.rule, closed.electorate, closed.approved), (ProposalTallyRule::get(), 150, false));
+
+		// only the gov origin may dispatch the call
+		assert!(SetValue(7).dispatch(Origin::signed(10)).is_err());
+	});
+}
+
+#[test]
+fn small_turnout_does_not_release_issue() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(GovModule::open_next(Origin::signed(10)));
+		// 10 of 150 voted, below the 20% turnout
+		assert_ok!(GovModule::vote(
+			Origin::signed(10),
+			GovVote {
+				aye: true,
+				balance: 10,
+				conviction: Conviction::None,
+			}
+		));
+		fast_forward_to(5);
+		assert_ok!(GovModule::over(Origin::signed(10)));
+		assert_eq!(GovModule::current_step(), 0);
+		assert!(!GovModule::alive());
+
+		// the rule and electorate are kept to check the result again
+		let prop = &GovModule::history_proposals()[0];
+		assert_eq!(prop.rule, IssueTallyRule::get());
+		assert_eq!(prop.electorate, Some(150));
+		assert!(!prop.rule.approved(prop.aye, prop.nay, prop.turnout, 150));
+	});
+}
+
+#[test]
+fn issue_needs_a_supermajority() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(GovModule::open_next(Origin::signed(10)));
+		assert_ok!(GovModule::vote(
+			Origin::signed(10),
+			GovVote {
+				aye: true,
+				balance: 50,
+				conviction: Conviction::None,
+			}
+		));
+		assert_ok!(GovModule::vote(
+			Origin::signed(20),
+			GovVote {
+				aye: false,
+				balance: 40,
+				conviction: Conviction::None,
+			}
+		));
+		fast_forward_to(5);
+		// 50 of 90 aye is a majority, not two thirds
+		assert_ok!(GovModule::over(Origin::signed(10)));
+		assert!(!GovModule::alive());
+	});
+}
+
+#[test]
+fn turnout_biased_thresholds() {
+	let approve = TallyRule {
+		turnout: Perbill::zero(),
+		threshold: VoteThreshold::SuperMajorityApprove,
+	};
+	// a low turnout needs a larger majority to pass
+	assert!(!approve.approved(6u64, 4, 10, 100));
+	assert!(approve.approved(9u64, 1, 10, 100));
+	assert!(approve.approved(60u64, 40, 100, 100));
+
+	let against = TallyRule {
+		turnout: Perbill::zero(),
+		threshold: VoteThreshold::SuperMajorityAgainst,
+	};
+	// and a larger majority to fail
+	assert!(against.approved(4u64, 6, 10, 100));
+	assert!(!against.approved(1u64, 9, 10, 100));
+	assert!(!against.approved(40u64, 60, 100, 100));
+
+	assert!(!approve.approved(0u64, 0, 0, 100));
+}
+
+#[test]
+fn conviction_weighs_votes_and_locks_after_close() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(GovModule::open_next(Origin::signed(10)));
+		// 40 with 3x conviction outweighs 50 without
+		assert_ok!(GovModule::vote(
+			Origin::signed(10),
+			GovVote {
+				aye: true,
+				balance: 40,
+				conviction: Conviction::Locked3x,
+			}
+		));
+		assert_ok!(GovModule::vote(
+			Origin::signed(20),
+			GovVote {
+				aye: false,
+				balance: 50,
+				conviction: Conviction::None,
+			}
+		));
+		fast_forward_to(5);
+		assert_ok!(GovModule::over(Origin::signed(10)));
+		assert_eq!(GovModule::current_step(), 1);
+		assert_eq!(GovModule::history_proposals()[0].turnout, 90);
+
+		// released without conviction
+		assert_ok!(GovModule::unlock(Origin::signed(20), 20));
+		assert_ok!(Balances::transfer(Origin::signed(20), 30, 10));
+		// kept for 2 periods from the end of the vote with it
+		assert_ok!(GovModule::unlock(Origin::signed(20), 10));
+		assert_eq!(GovModule::conviction_locks(10), vec![(22, 40)]);
+		assert_noop!(
+			Balances::transfer(Origin::signed(10), 30, 11),
+			BalancesError::<Test, _>::LiquidityRestrictions
+		);
+		fast_forward_to(21);
+		assert_ok!(GovModule::unlock(Origin::signed(20), 10));
+		assert_noop!(
+			Balances::transfer(Origin::signed(10), 30, 11),
+			BalancesError::<Test, _>::LiquidityRestrictions
+		);
+
+		fast_forward_to(22);
+		assert_ok!(GovModule::unlock(Origin::signed(20), 10));
+		assert!(GovModule::conviction_locks(10).is_empty());
+		assert_ok!(Balances::transfer(Origin::signed(10), 30, 11));
+	});
+}
+
+#[test]
+fn anyone_closes_finished_votes() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			GovModule::open_next(Origin::signed(20)),
+			Error::<Test>::NotOperateAccount
+		);
+		assert_ok!(GovModule::open_next(Origin::root()));
+		assert_ok!(GovModule::propose(Origin::signed(10), Box::new(SetValue(7)), H256::zero()));
+		assert_ok!(GovModule::vote_proposal(
+			Origin::signed(20),
+			0,
+			GovVote { aye: true, balance: 40, conviction: Conviction::None }
+		));
+		fast_forward_to(5);
+		assert_ok!(GovModule::over(Origin::signed(30)));
+		// the bound must cover the weight of the call
+		assert_noop!(
+			GovModule::close_proposal(Origin::signed(30), 0, 99),
+			Error::<Test>::WrongProposalWeight
+		);
+		assert_ok!(GovModule::close_proposal(Origin::signed(30), 0, 100));
+		assert_eq!(GOV_VALUE.with(|v| *v.borrow()), 7);
+		assert_eq!(GovModule::history_proposals().len(), 1);
+	});
+}
+
+#[test]
+fn finished_votes_close_on_initialize() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(GovModule::open_next(Origin::signed(10)));
+		assert_ok!(GovModule::propose(Origin::signed(10), Box::new(SetValue(7)), H256::zero()));
+		assert_ok!(GovModule::vote(
+			Origin::signed(20),
+			GovVote {
+				aye: true,
+				balance: 50,
+				conviction: Conviction::None,
+			}
+		));
+		assert_ok!(GovModule::vote_proposal(
+			Origin::signed(20),
+			0,
+			GovVote { aye: true, balance: 50, conviction: Conviction::None }
+		));
+
+		GovModule::on_initialize(1);
+		assert!(GovModule::opened_proposal().is_some());
+
+		fast_forward_to(5);
+		GovModule::on_initialize(2);
+		assert_eq!(GovModule::current_step(), 1);
+		assert!(GovModule::opened_proposal().is_none());
+		assert!(GovModule::gov_proposals(0).is_none());
+		assert_eq!(GOV_VALUE.with(|v| *v.borrow()), 7);
+		// locks are released
+		assert_ok!(GovModule::unlock(Origin::signed(20), 20));
+		assert_ok!(Balances::transfer(Origin::signed(20), 30, 50));
+	});
+}
+
+#[test]
+fn proposals_close_on_initialize_by_end() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(GovModule::propose(Origin::signed(10), Box::new(SetValue(7)), H256::zero()));
+		assert_ok!(GovModule::reset_vote_period(Origin::root(), 1));
+		assert_ok!(GovModule::propose(Origin::signed(10), Box::new(SetValue(8)), H256::zero()));
+		assert_ok!(GovModule::propose(Origin::signed(10), Box::new(SetValue(9)), H256::zero()));
+		assert_eq!(GovModule::proposal_ends(), vec![(1, 1), (1, 2), (2, 0)]);
+		assert_ok!(GovModule::vote_proposal(
+			Origin::signed(20),
+			2,
+			GovVote { aye: true, balance: 50, conviction: Conviction::None }
+		));
+
+		// the proposal still open stops the closes
+		fast_forward_to(2);
+		GovModule::on_initialize(1);
+		assert_eq!(GovModule::proposal_ends(), vec![(2, 0)]);
+		assert_eq!(GOV_VALUE.with(|v| *v.borrow()), 9);
+
+		// at most two closes a block, the call weighs in
+		assert_ok!(GovModule::propose(Origin::signed(20), Box::new(SetValue(10)), H256::zero()));
+		assert_ok!(GovModule::propose(Origin::signed(20), Box::new(SetValue(11)), H256::zero()));
+		fast_forward_to(4);
+		GovModule::on_initialize(2);
+		assert_eq!(GovModule::proposal_ends(), vec![(3, 4)]);
+		assert!(GovModule::gov_proposals(4).is_some());
+		GovModule::on_initialize(3);
+		assert!(GovModule::proposal_ends().is_empty());
+		assert!(GovModule::gov_proposals(4).is_none());
+		assert_eq!(Balances::reserved_balance(10), 0);
+		assert_eq!(Balances::reserved_balance(20), 0);
+	});
+}
+
+#[test]
+fn next_stage_opens_on_schedule() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			GovModule::schedule_open(Origin::signed(20), 10),
+			Error::<Test>::NotOperateAccount
+		);
+		assert_ok!(GovModule::schedule_open(Origin::root(), 10));
+
+		fast_forward_to(9);
+		GovModule::on_initialize(1);
+		assert!(GovModule::opened_proposal().is_none());
+
+		fast_forward_to(10);
+		GovModule::on_initialize(2);
+		assert!(GovModule::opened_proposal().is_some());
+		assert_eq!(GovModule::scheduled_open(), None);
+	});
+}
+
+#[test]
+fn recover_after_failed_vote() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			GovModule::recover(Origin::root(), false, None, None),
+			Error::<Test>::NotBroken
+		);
+		assert_ok!(GovModule::open_next(Origin::signed(10)));
+		fast_forward_to(5);
+		assert_ok!(GovModule::over(Origin::signed(10)));
+		assert!(!GovModule::alive());
+
+		assert_noop!(GovModule::recover(Origin::signed(10), true, None, None), BadOrigin);
+		let rule = TallyRule {
+			turnout: Perbill::from_percent(5),
+			threshold: VoteThreshold::SimpleMajority,
+		};
+		assert_ok!(GovModule::recover(Origin::root(), true, Some(10), Some(rule)));
+		assert!(GovModule::alive());
+		let history = GovModule::history_proposals();
+		assert_eq!(history.len(), 2);
+		assert_eq!(history[1].step, 1);
+		assert!(history[1].recovery);
+
+		// the failed step runs again with the new parameters
+		let prop = GovModule::opened_proposal().unwrap();
+		assert_eq!((prop.step, prop.end, prop.rule), (1, 15, rule));
+		assert_ok!(GovModule::vote(
+			Origin::signed(20),
+			GovVote {
+				aye: true,
+				balance: 10,
+				conviction: Conviction::None,
+			}
+		));
+		fast_forward_to(16);
+		assert_ok!(GovModule::over(Origin::signed(10)));
+		assert_eq!(GovModule::current_step(), 1);
+	});
+}
+
+#[test]
+fn issue_votes_migrate_to_v2() {
+	new_test_ext().execute_with(|| {
+		// as stored before the tally rules
+		crate::StorageVersion::kill();
+		unhashed::put(&<crate::OpenedProposal<Test>>::hashed_key(), &(2u32, 0u64, 2u64, 30u64, 10u64));
+		unhashed::put(&<crate::HistoryProposals<Test>>::hashed_key(), &vec![(1u32, 0u64, 2u64, 40u64, 0u64)]);
+		unhashed::put(&<crate::Votes<Test>>::hashed_key_for(2, 20), &(true, 30u64));
+		<crate::VotingOf<Test>>::insert(2, vec![20]);
+
+		GovModule::on_runtime_upgrade();
+		assert_eq!(
+			GovModule::opened_proposal(),
+			Some(Proposal {
+				step: 2,
+				start: 0,
+				end: 2,
+				aye: 30,
+				nay: 10,
+				turnout: 40,
+				rule: TallyRule::default(),
+				electorate: None,
+				recovery: false,
+			})
+		);
+		let history = GovModule::history_proposals();
+		assert_eq!((history.len(), history[0].aye, history[0].turnout), (1, 40, 40));
+		assert_eq!(GovModule::votes(2, 20), GovVote { aye: true, balance: 30, conviction: Conviction::None });
+		assert_eq!(GovModule::issue_vote_of(20), Some((2, 2)));
+
+		// only once
+		GovModule::on_runtime_upgrade();
+		assert_eq!(GovModule::history_proposals(), history);
+	});
+}
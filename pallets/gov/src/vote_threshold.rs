@@ -0,0 +1,97 @@
+//! Rules a tally has to satisfy for a proposal to pass.
+
+use codec::{Decode, Encode};
+use sp_runtime::{
+	Perbill, PerThing, RuntimeDebug,
+	traits::{AtLeast32BitUnsigned, IntegerSquareRoot, SaturatedConversion, Saturating, Zero},
+};
+use sp_std::ops::{Div, Mul, Rem};
+
+/// Majority the aye votes need over the nay votes.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+pub enum VoteThreshold {
+	/// More aye than nay.
+	SimpleMajority,
+	/// At least this share of the votes cast are aye.
+	SuperMajority(Perbill),
+	/// A low turnout needs a larger majority to pass: `nay / sqrt(turnout) < aye / sqrt(electorate)`.
+	SuperMajorityApprove,
+	/// A low turnout needs a larger majority to fail: `nay / sqrt(electorate) < aye / sqrt(turnout)`.
+	SuperMajorityAgainst,
+}
+
+impl Default for VoteThreshold {
+	fn default() -> Self {
+		VoteThreshold::SimpleMajority
+	}
+}
+
+/// Tally rule of a proposal, recorded with it so its result can be checked again later.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+pub struct TallyRule {
+	/// Minimum share of the electorate which has to vote.
+	pub turnout: Perbill,
+	pub threshold: VoteThreshold,
+}
+
+impl TallyRule {
+	/// Whether `aye` against `nay` votes pass when `turnout` out of `electorate` voted.
+	/// Votes may be weighted by conviction, `turnout` is the balance which voted.
+	pub fn approved<Balance: AtLeast32BitUnsigned + Copy>(
+		&self,
+		aye: Balance,
+		nay: Balance,
+		turnout: Balance,
+		electorate: Balance,
+	) -> bool {
+		if turnout.is_zero() || electorate.is_zero() {
+			return false;
+		}
+		let turnout = turnout.min(electorate);
+		if turnout.saturated_into::<u128>() < self.turnout.mul_ceil(electorate.saturated_into::<u128>()) {
+			return false;
+		}
+		let (aye_votes, votes) = (aye.saturated_into::<u128>(), aye.saturating_add(nay).saturated_into::<u128>());
+
+		match self.threshold {
+			VoteThreshold::SimpleMajority => aye > nay,
+			VoteThreshold::SuperMajority(share) => aye > nay && aye_votes >= share.mul_ceil(votes),
+			VoteThreshold::SuperMajorityApprove =>
+				compare_rationals(nay, turnout.integer_sqrt(), aye, electorate.integer_sqrt()),
+			VoteThreshold::SuperMajorityAgainst =>
+				compare_rationals(nay, electorate.integer_sqrt(), aye, turnout.integer_sqrt()),
+		}
+	}
+}
+
+/// Return `true` iff `n1 / d1 < n2 / d2`. `d1` and `d2` may not be zero.
+fn compare_rationals<T: Zero + Mul<T, Output = T> + Div<T, Output = T> + Rem<T, Output = T> + Ord + Copy>(
+	mut n1: T,
+	mut d1: T,
+	mut n2: T,
+	mut d2: T,
+) -> bool {
+	// Uses a continued fractional representation for a non-overflowing compare.
+	loop {
+		let q1 = n1 / d1;
+		let q2 = n2 / d2;
+		if q1 < q2 {
+			return true;
+		}
+		if q2 < q1 {
+			return false;
+		}
+		let r1 = n1 % d1;
+		let r2 = n2 % d2;
+		if r2.is_zero() {
+			return false;
+		}
+		if r1.is_zero() {
+			return true;
+		}
+		n1 = d2;
+		n2 = d1;
+		d1 = r2;
+		d2 = r1;
+	}
+}
@@ -0,0 +1,221 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Balances of the rtokens, one ledger for each `RSymbol`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::DispatchResult, ensure,
+    traits::EnsureOrigin,
+};
+use frame_system::{self as system, ensure_signed, ensure_root};
+use sp_runtime::traits::{StaticLookup, Zero};
+use node_primitives::RSymbol;
+
+pub mod traits;
+use traits::Currency;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub trait Trait: system::Trait {
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+    /// Origin allowed to mint rtokens
+    type MintOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin allowed to burn rtokens
+    type BurnOrigin: EnsureOrigin<Self::Origin>;
+}
+
+decl_event! {
+    pub enum Event<T> where
+        AccountId = <T as system::Trait>::AccountId,
+    {
+        /// Transfer succeeded (from, to, symbol, value)
+        Transfer(AccountId, AccountId, RSymbol, u128),
+        /// Some rtoken was minted (who, symbol, value)
+        Minted(AccountId, RSymbol, u128),
+        /// Some rtoken was burned (who, symbol, value)
+        Burned(AccountId, RSymbol, u128),
+        /// An account fell below the existential deposit and its balance was dropped (who, symbol, value)
+        DustLost(AccountId, RSymbol, u128),
+        /// Existential deposit of a symbol changed
+        ExistentialDepositSet(RSymbol, u128),
+    }
+}
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        /// Balance too low to send value
+        InsufficientBalance,
+        /// Value too low to create account due to existential deposit
+        ExistentialDeposit,
+        /// Got an overflow after adding
+        Overflow,
+    }
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as RBalances {
+        /// Free balance of each account, for each symbol
+        pub Balances get(fn balances):
+            double_map hasher(blake2_128_concat) RSymbol, hasher(blake2_128_concat) T::AccountId => u128;
+
+        /// The total units issued in the system, for each symbol
+        pub TotalIssuance get(fn total_issuance): map hasher(twox_64_concat) RSymbol => u128;
+
+        /// The minimum balance an account may have, for each symbol
+        pub ExistentialDeposit get(fn existential_deposit) config(): map hasher(twox_64_concat) RSymbol => u128;
+    }
+    add_extra_genesis {
+        config(balances): Vec<(T::AccountId, RSymbol, u128)>;
+        build(|config| {
+            for (who, symbol, value) in &config.balances {
+                assert!(
+                    *value >= <ExistentialDeposit>::get(symbol),
+                    "the balance of any account should always be at least the existential deposit.",
+                );
+                <Balances<T>>::insert(symbol, who, value);
+                <TotalIssuance>::mutate(symbol, |t| *t = t.saturating_add(*value));
+            }
+        });
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Transfer some free balance of `symbol` to another account.
+        #[weight = 195_000_000]
+        pub fn transfer(origin, dest: <T::Lookup as StaticLookup>::Source, symbol: RSymbol, #[compact] value: u128) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+            <Self as Currency<_>>::transfer(&who, &dest, symbol, value)
+        }
+
+        /// Mint `value` of `symbol` into the account of `dest`.
+        #[weight = 100_000_000]
+        pub fn mint_to(origin, dest: <T::Lookup as StaticLookup>::Source, symbol: RSymbol, #[compact] value: u128) -> DispatchResult {
+            T::MintOrigin::ensure_origin(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+            <Self as Currency<_>>::mint(&dest, symbol, value)
+        }
+
+        /// Burn `value` of `symbol` from the account of `who`.
+        #[weight = 100_000_000]
+        pub fn burn_from(origin, who: <T::Lookup as StaticLookup>::Source, symbol: RSymbol, #[compact] value: u128) -> DispatchResult {
+            T::BurnOrigin::ensure_origin(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            <Self as Currency<_>>::burn(&who, symbol, value)
+        }
+
+        /// Set the existential deposit of `symbol`.
+        ///
+        /// Accounts already below it are only dropped the next time their balance decreases.
+        #[weight = 10_000]
+        pub fn set_existential_deposit(origin, symbol: RSymbol, value: u128) -> DispatchResult {
+            ensure_root(origin)?;
+            <ExistentialDeposit>::insert(symbol, value);
+
+            Self::deposit_event(RawEvent::ExistentialDepositSet(symbol, value));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Writes the free balance of `who`, dropping it if it fell below the existential deposit.
+    fn set_balance(who: &T::AccountId, symbol: RSymbol, balance: u128) {
+        if balance.is_zero() {
+            <Balances<T>>::remove(symbol, who);
+        } else if balance < Self::existential_deposit(symbol) {
+            <Balances<T>>::remove(symbol, who);
+            <TotalIssuance>::mutate(symbol, |t| *t = t.saturating_sub(balance));
+            Self::deposit_event(RawEvent::DustLost(who.clone(), symbol, balance));
+        } else {
+            <Balances<T>>::insert(symbol, who, balance);
+        }
+    }
+}
+
+impl<T: Trait> Currency<T::AccountId> for Module<T> {
+    fn total_issuance(symbol: RSymbol) -> u128 {
+        <TotalIssuance>::get(symbol)
+    }
+
+    fn minimum_balance(symbol: RSymbol) -> u128 {
+        Self::existential_deposit(symbol)
+    }
+
+    fn free_balance(who: &T::AccountId, symbol: RSymbol) -> u128 {
+        Self::balances(symbol, who)
+    }
+
+    fn ensure_can_withdraw(who: &T::AccountId, symbol: RSymbol, amount: u128, _new_balance: u128) -> DispatchResult {
+        ensure!(Self::balances(symbol, who) >= amount, Error::<T>::InsufficientBalance);
+        Ok(())
+    }
+
+    fn transfer(source: &T::AccountId, dest: &T::AccountId, symbol: RSymbol, value: u128) -> DispatchResult {
+        if value.is_zero() || source == dest {
+            return Ok(());
+        }
+        let from_balance = Self::balances(symbol, source).checked_sub(value)
+            .ok_or(Error::<T>::InsufficientBalance)?;
+        let to_balance = Self::balances(symbol, dest).checked_add(value)
+            .ok_or(Error::<T>::Overflow)?;
+        ensure!(to_balance >= Self::existential_deposit(symbol), Error::<T>::ExistentialDeposit);
+
+        Self::set_balance(source, symbol, from_balance);
+        <Balances<T>>::insert(symbol, dest, to_balance);
+
+        Self::deposit_event(RawEvent::Transfer(source.clone(), dest.clone(), symbol, value));
+        Ok(())
+    }
+
+    fn mint(who: &T::AccountId, symbol: RSymbol, value: u128) -> DispatchResult {
+        if value.is_zero() {
+            return Ok(());
+        }
+        let issuance = Self::total_issuance(symbol).checked_add(value)
+            .ok_or(Error::<T>::Overflow)?;
+        let balance = Self::balances(symbol, who).checked_add(value)
+            .ok_or(Error::<T>::Overflow)?;
+        ensure!(balance >= Self::existential_deposit(symbol), Error::<T>::ExistentialDeposit);
+
+        <TotalIssuance>::insert(symbol, issuance);
+        <Balances<T>>::insert(symbol, who, balance);
+
+        Self::deposit_event(RawEvent::Minted(who.clone(), symbol, value));
+        Ok(())
+    }
+
+    fn burn(who: &T::AccountId, symbol: RSymbol, value: u128) -> DispatchResult {
+        if value.is_zero() {
+            return Ok(());
+        }
+        let balance = Self::balances(symbol, who).checked_sub(value)
+            .ok_or(Error::<T>::InsufficientBalance)?;
+
+        <TotalIssuance>::mutate(symbol, |t| *t = t.saturating_sub(value));
+        Self::set_balance(who, symbol, balance);
+
+        Self::deposit_event(RawEvent::Burned(who.clone(), symbol, value));
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the module.
+
+use super::*;
+use super::mock::*;
+use frame_support::{assert_ok, assert_noop};
+use sp_runtime::traits::BadOrigin;
+
+fn last_event() -> TestEvent {
+    system::Module::<Test>::events().pop().map(|e| e.event).expect("Event expected")
+}
+
+#[test]
+fn genesis_config_works() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(RBalances::free_balance(&ALICE, RSymbol::RFIS), 100);
+        assert_eq!(RBalances::free_balance(&ALICE, RSymbol::RDOT), 50);
+        assert_eq!(RBalances::total_issuance(RSymbol::RFIS), 100);
+        assert_eq!(RBalances::minimum_balance(RSymbol::RDOT), 5);
+        assert_eq!(RBalances::minimum_balance(RSymbol::RKSM), 0);
+    });
+}
+
+#[test]
+fn transfer_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RBalances::transfer(Origin::signed(ALICE), BOB, RSymbol::RFIS, 30));
+        assert_eq!(last_event(), TestEvent::rtoken_balances(RawEvent::Transfer(ALICE, BOB, RSymbol::RFIS, 30)));
+        assert_eq!(RBalances::free_balance(&ALICE, RSymbol::RFIS), 70);
+        assert_eq!(RBalances::free_balance(&BOB, RSymbol::RFIS), 30);
+        // symbols are kept apart
+        assert_eq!(RBalances::free_balance(&BOB, RSymbol::RDOT), 0);
+
+        assert_noop!(
+            RBalances::transfer(Origin::signed(BOB), ALICE, RSymbol::RFIS, 31),
+            Error::<Test>::InsufficientBalance,
+        );
+        assert_eq!(RBalances::total_issuance(RSymbol::RFIS), 100);
+    });
+}
+
+#[test]
+fn transfer_respects_existential_deposit() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            RBalances::transfer(Origin::signed(ALICE), BOB, RSymbol::RDOT, 4),
+            Error::<Test>::ExistentialDeposit,
+        );
+
+        // what is left below the existential deposit is dropped
+        assert_ok!(RBalances::transfer(Origin::signed(ALICE), BOB, RSymbol::RDOT, 47));
+        assert_eq!(last_event(), TestEvent::rtoken_balances(RawEvent::Transfer(ALICE, BOB, RSymbol::RDOT, 47)));
+        assert_eq!(RBalances::free_balance(&ALICE, RSymbol::RDOT), 0);
+        assert_eq!(RBalances::free_balance(&BOB, RSymbol::RDOT), 47);
+        assert_eq!(RBalances::total_issuance(RSymbol::RDOT), 47);
+    });
+}
+
+#[test]
+fn mint_and_burn_are_restricted() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(RBalances::mint_to(Origin::signed(ALICE), BOB, RSymbol::RKSM, 10), BadOrigin);
+        assert_ok!(RBalances::mint_to(Origin::signed(Minter::get()), BOB, RSymbol::RKSM, 10));
+        assert_eq!(last_event(), TestEvent::rtoken_balances(RawEvent::Minted(BOB, RSymbol::RKSM, 10)));
+        assert_eq!(RBalances::free_balance(&BOB, RSymbol::RKSM), 10);
+        assert_eq!(RBalances::total_issuance(RSymbol::RKSM), 10);
+
+        assert_noop!(RBalances::burn_from(Origin::signed(Minter::get()), BOB, RSymbol::RKSM, 4), BadOrigin);
+        assert_noop!(
+            RBalances::burn_from(Origin::root(), BOB, RSymbol::RKSM, 11),
+            Error::<Test>::InsufficientBalance,
+        );
+        assert_ok!(RBalances::burn_from(Origin::root(), BOB, RSymbol::RKSM, 4));
+        assert_eq!(last_event(), TestEvent::rtoken_balances(RawEvent::Burned(BOB, RSymbol::RKSM, 4)));
+        assert_eq!(RBalances::free_balance(&BOB, RSymbol::RKSM), 6);
+        assert_eq!(RBalances::total_issuance(RSymbol::RKSM), 6);
+    });
+}
+
+#[test]
+fn set_existential_deposit_works() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(RBalances::set_existential_deposit(Origin::signed(ALICE), RSymbol::RFIS, 10), BadOrigin);
+        assert_ok!(RBalances::set_existential_deposit(Origin::root(), RSymbol::RFIS, 10));
+        assert_eq!(RBalances::minimum_balance(RSymbol::RFIS), 10);
+
+        assert_noop!(
+            <RBalances as Currency<_>>::mint(&BOB, RSymbol::RFIS, 9),
+            Error::<Test>::ExistentialDeposit,
+        );
+        assert_ok!(<RBalances as Currency<_>>::mint(&BOB, RSymbol::RFIS, 10));
+    });
+}
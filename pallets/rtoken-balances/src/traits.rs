@@ -0,0 +1,40 @@
+// Copyright 2019-2020 Parity Protocol.
+// This file is part of Parity.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Traits for dealing with rtoken balances.
+
+use frame_support::dispatch::DispatchResult;
+use node_primitives::RSymbol;
+
+/// Abstraction over a multi-asset fungible system, keyed by `RSymbol`.
+pub trait Currency<AccountId> {
+    /// The total amount of `symbol` in the system.
+    fn total_issuance(symbol: RSymbol) -> u128;
+
+    /// The minimum balance an account of `symbol` may have.
+    fn minimum_balance(symbol: RSymbol) -> u128;
+
+    /// The free balance of `who` in `symbol`.
+    fn free_balance(who: &AccountId, symbol: RSymbol) -> u128;
+
+    /// Returns `Ok` iff `amount` of `symbol` can be withdrawn from `who`,
+    /// leaving `new_balance` behind.
+    fn ensure_can_withdraw(who: &AccountId, symbol: RSymbol, amount: u128, new_balance: u128) -> DispatchResult;
+
+    /// Transfer some liquid free balance to another account.
+    fn transfer(source: &AccountId, dest: &AccountId, symbol: RSymbol, value: u128) -> DispatchResult;
+
+    /// Adds `value` to the free balance of `who`, increasing the total issuance.
+    fn mint(who: &AccountId, symbol: RSymbol, value: u128) -> DispatchResult;
+
+    /// Removes `value` from the free balance of `who`, reducing the total issuance.
+    fn burn(who: &AccountId, symbol: RSymbol, value: u128) -> DispatchResult;
+}